@@ -0,0 +1,157 @@
+//! Simulated annealing over the `Board` using the number of checks as the energy.
+//!
+//! A random queen is moved to a random empty square. Moves which lower the energy are always
+//! taken and the others are taken with the Boltzmann probability `e^(-delta/T)`, so the search can
+//! climb out of the local minima where the greedy `Random` mode gets stuck.
+
+use rand::prelude::*;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Board, Error};
+
+/// How the temperature falls with the steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// `T = T0 * rate^step`
+    Exponential,
+    /// `T = max(T0 - rate * step, 0)`
+    Linear,
+    /// `T = T0 / (1 + rate * ln(1 + step))`
+    Logarithmic,
+}
+
+impl Schedule {
+    /// The temperature after some steps (since the start or the last reheat).
+    pub fn temperature(&self, initial: f64, rate: f64, step: usize) -> f64 {
+        let step = step as f64;
+        match self {
+            Self::Exponential => initial * rate.powf(step),
+            Self::Linear => (initial - rate * step).max(0.0),
+            Self::Logarithmic => initial / (1.0 + rate * step.ln_1p()),
+        }
+    }
+
+    /// A rate which cools a starting temperature of 2 over a few thousand steps.
+    pub fn default_rate(&self) -> f64 {
+        match self {
+            Self::Exponential => 0.999,
+            Self::Linear => 0.0004,
+            Self::Logarithmic => 1.0,
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exponential" => Ok(Self::Exponential),
+            "linear" => Ok(Self::Linear),
+            "logarithmic" => Ok(Self::Logarithmic),
+            _ => Err(Error::UnknownSchedule(s.to_string())),
+        }
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Exponential => "exponential",
+            Self::Linear => "linear",
+            Self::Logarithmic => "logarithmic",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The parameters of an annealing run.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub schedule: Schedule,
+    /// The temperature at the start (and after each reheat).
+    pub initial_temperature: f64,
+    /// The parameter of the schedule (see `Schedule`).
+    pub cooling_rate: f64,
+    /// Restart the schedule after this many steps without a new best energy.
+    pub reheat_after: Option<usize>,
+    /// The maximum number of moves tried.
+    pub max_steps: usize,
+}
+
+/// The outcome of an annealing run.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The board with the lowest energy seen.
+    pub best: Board,
+    /// The number of steps taken.
+    pub steps: usize,
+    /// The number of moves which raised the energy and were accepted.
+    pub uphill_moves: usize,
+    /// The number of times the temperature was restarted.
+    pub reheats: usize,
+}
+
+/// Anneal the board until no checks are left or the steps run out.
+///
+/// # Caveats
+/// - Loops forever if the board is filled as it places the queens randomly.
+pub fn anneal<R: Rng>(mut board: Board, options: &Options, rng: &mut R) -> Report {
+    let mut energy = board.checks_count();
+    let mut best = board.clone();
+    let mut best_energy = energy;
+    let mut uphill_moves = 0;
+    let mut reheats = 0;
+    // The step of the schedule, reset by reheating.
+    let mut clock = 0;
+    let mut since_best = 0;
+
+    let mut step = 0;
+    while step < options.max_steps && best_energy > 0 && !board.queens().is_empty() {
+        step += 1;
+        clock += 1;
+        let temperature =
+            options
+                .schedule
+                .temperature(options.initial_temperature, options.cooling_rate, clock);
+
+        let from = board.queens()[rng.gen_range(0..board.queens().len())].clone();
+        let mut to = board.random_point(rng);
+        while board.mov(&from, &to).is_err() {
+            to = board.random_point(rng);
+        }
+        let new_energy = board.checks_count();
+
+        if new_energy <= energy {
+            energy = new_energy;
+        } else if temperature > 0.0
+            && rng.gen::<f64>() < (-((new_energy - energy) as f64) / temperature).exp()
+        {
+            energy = new_energy;
+            uphill_moves += 1;
+        } else {
+            board.mov(&to, &from).unwrap();
+        }
+
+        if energy < best_energy {
+            best_energy = energy;
+            best = board.clone();
+            since_best = 0;
+        } else {
+            since_best += 1;
+        }
+        if options.reheat_after.map_or(false, |k| since_best >= k) {
+            clock = 0;
+            since_best = 0;
+            reheats += 1;
+        }
+    }
+
+    Report {
+        best,
+        steps: step,
+        uphill_moves,
+        reheats,
+    }
+}
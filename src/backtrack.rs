@@ -0,0 +1,74 @@
+//! An exact depth-first backtracking solver.
+//!
+//! Unlike the heuristic modes, this always finds a placement if one exists.
+
+use crate::Board;
+
+/// The state of the search: which column each placed row's queen is in and the taken lines.
+struct Search {
+    n: usize,
+    /// The column of the queen in each row placed so far.
+    cols: Vec<usize>,
+    used_cols: Vec<bool>,
+    /// Diagonals indexed by `row + col`.
+    used_diags: Vec<bool>,
+    /// Anti-diagonals indexed by `row + n - 1 - col`.
+    used_anti_diags: Vec<bool>,
+}
+
+impl Search {
+    fn new(n: usize) -> Self {
+        let diags = (2 * n).saturating_sub(1);
+        Self {
+            n,
+            cols: Vec::with_capacity(n),
+            used_cols: vec![false; n],
+            used_diags: vec![false; diags],
+            used_anti_diags: vec![false; diags],
+        }
+    }
+
+    /// Mark (or unmark) the lines going through a square.
+    fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.used_cols[col] = value;
+        self.used_diags[row + col] = value;
+        self.used_anti_diags[row + self.n - 1 - col] = value;
+    }
+
+    fn is_free(&self, row: usize, col: usize) -> bool {
+        !self.used_cols[col]
+            && !self.used_diags[row + col]
+            && !self.used_anti_diags[row + self.n - 1 - col]
+    }
+
+    /// Place a queen on each remaining row, undoing the choices that lead to dead ends.
+    fn fill(&mut self) -> bool {
+        let row = self.cols.len();
+        if row == self.n {
+            return true;
+        }
+        for col in 0..self.n {
+            if self.is_free(row, col) {
+                self.set(row, col, true);
+                self.cols.push(col);
+                if self.fill() {
+                    return true;
+                }
+                self.cols.pop();
+                self.set(row, col, false);
+            }
+        }
+        false
+    }
+}
+
+/// Find a placement of `n` non-threatening queens on an `n*n` board by backtracking row by row.
+///
+/// Returns nothing if no solution exists (only the case for `n` of 2 and 3).
+pub fn solve(n: usize) -> Option<Board> {
+    let mut search = Search::new(n);
+    if !search.fill() {
+        return None;
+    }
+    Some(Board::from_permutation(&search.cols).expect("The search places one queen per column"))
+}
@@ -0,0 +1,89 @@
+//! Local beam search over `k` boards.
+//!
+//! Every step generates all the successors (single-queen moves) of every board and keeps `k` of
+//! them. The plain variant keeps the `k` with the fewest checks, the stochastic variant samples
+//! them with a chance proportional to their fitness (`max_checks - checks_count`).
+
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+use crate::{Board, Error, Point};
+
+/// The parameters of a beam search.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The number of boards kept at each step.
+    pub k: usize,
+    /// Sample the successors by their fitness instead of keeping the best ones.
+    pub stochastic: bool,
+    /// The maximum number of steps.
+    pub max_steps: usize,
+}
+
+/// The outcome of a beam search.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The board with the fewest checks seen.
+    pub best: Board,
+    /// The number of steps taken.
+    pub steps: usize,
+}
+
+/// Search from `k` random boards of `n` queens until no checks are left or the steps run out.
+pub fn search<R: Rng>(n: usize, options: &Options, rng: &mut R) -> Result<Report, Error> {
+    let mut beam = vec![Board::new(n); options.k]
+        .into_iter()
+        .map(|b| b.init_n_queens(rng))
+        .collect::<Result<Vec<Board>, Error>>()?;
+    let mut best = beam
+        .iter()
+        .min_by_key(|b| b.checks_count())
+        .ok_or(Error::EmptyBeam)?
+        .clone();
+
+    let mut step = 0;
+    while step < options.max_steps && best.checks_count() > 0 {
+        step += 1;
+
+        // All the successors as (index of the board in the beam, from, to, checks after).
+        let mut successors = Vec::<(usize, Point, Point, usize)>::new();
+        for (i, board) in beam.iter().enumerate() {
+            for (from, to, cost) in board.moves() {
+                successors.push((i, from, to, cost));
+            }
+        }
+        if successors.is_empty() {
+            break;
+        }
+
+        let picked = if options.stochastic {
+            let max_checks = best.max_checks();
+            let dist = WeightedIndex::new(successors.iter().map(|s| max_checks - s.3 + 1))
+                .expect("Every successor has a positive weight");
+            (0..options.k)
+                .map(|_| successors[dist.sample(rng)].clone())
+                .collect::<Vec<_>>()
+        } else {
+            // Shuffle first so the ties are broken randomly by the stable sort.
+            successors.shuffle(rng);
+            successors.sort_by_key(|s| s.3);
+            successors.truncate(options.k);
+            successors
+        };
+
+        beam = picked
+            .into_iter()
+            .map(|(i, from, to, _)| {
+                let mut board = beam[i].clone();
+                board.mov(&from, &to).map(|_| board)
+            })
+            .collect::<Result<Vec<Board>, Error>>()?;
+        for board in &beam {
+            if board.checks_count() < best.checks_count() {
+                best = board.clone();
+            }
+        }
+    }
+
+    Ok(Report { best, steps: step })
+}
@@ -0,0 +1,108 @@
+//! Explicit construction of a solution without any search.
+//!
+//! A solution exists for every `n` other than 2 and 3, and one can be written down directly
+//! depending on `n mod 6` (Hoffman, Loessi and Moore, 1969). Numbering the rows and columns from
+//! 1, the queens of the rows are put on these columns in order:
+//!
+//! - `n mod 6` not 2 or 3: the even columns `2, 4, ...` then the odd ones `1, 3, ...`.
+//! - `n mod 6 = 2`: the even columns, then `3, 1, 7, 9, 11, ..., 5` (1 and 3 swapped, 5 last).
+//! - `n mod 6 = 3`: `4, 6, ..., 2` (2 last), then `5, 7, ..., 1, 3` (1 and 3 last).
+
+use crate::Board;
+
+/// The column of the queen on each row of a solution for `n` queens.
+///
+/// Returns nothing if no solution exists (`n` of 2 and 3). This takes `O(n)` time and memory so
+/// it is fit for boards far larger than what `Board` can hold.
+pub fn permutation(n: usize) -> Option<Vec<usize>> {
+    if n == 2 || n == 3 {
+        return None;
+    }
+    // The columns are 1-indexed here to match the pattern.
+    let evens = (2..=n).step_by(2);
+    let odds = (1..=n).step_by(2);
+    let cols: Vec<usize> = match n % 6 {
+        2 => evens
+            .chain([3, 1])
+            .chain(odds.filter(|&c| c > 5))
+            .chain([5])
+            .collect(),
+        3 => evens
+            .filter(|&c| c != 2)
+            .chain([2])
+            .chain(odds.filter(|&c| c > 3))
+            .chain([1, 3])
+            .collect(),
+        _ => evens.chain(odds).collect(),
+    };
+    Some(cols.into_iter().map(|c| c - 1).collect())
+}
+
+/// Build a solution for `n` queens as a `Board`.
+///
+/// Returns nothing if no solution exists (`n` of 2 and 3).
+///
+/// # Caveats
+/// - `Board` caches the threats of every pair of queens, this is slow for large `n`.
+pub fn board(n: usize) -> Option<Board> {
+    permutation(n).map(|cols| {
+        Board::from_permutation(&cols).expect("The patterns place one queen per column")
+    })
+}
+
+/// Check if the queen of each row on the given column make a solution.
+///
+/// Unlike `Board::checking` which compares every pair, each column and diagonal is marked once
+/// taken, so this is `O(n)`.
+pub fn verify(cols: &[usize]) -> bool {
+    let n = cols.len();
+    let diags = (2 * n).saturating_sub(1);
+    let mut used_cols = vec![false; n];
+    let mut used_diags = vec![false; diags];
+    let mut used_anti_diags = vec![false; diags];
+    for (row, &col) in cols.iter().enumerate() {
+        if col >= n {
+            return false;
+        }
+        for used in [
+            &mut used_cols[col],
+            &mut used_diags[row + col],
+            &mut used_anti_diags[row + n - 1 - col],
+        ] {
+            if *used {
+                return false;
+            }
+            *used = true;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permutations_are_solutions() {
+        for n in 1..=3000 {
+            match permutation(n) {
+                Some(cols) => assert!(verify(&cols), "n = {}", n),
+                None => assert!(n == 2 || n == 3, "n = {}", n),
+            }
+        }
+        for n in [1, 4, 5, 6, 7, 8, 9, 14, 15] {
+            assert_eq!(board(n).unwrap().checks_count(), 0, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn verify_rejects_shared_lines() {
+        assert!(verify(&[1, 3, 0, 2]));
+        // A shared diagonal (`row + col`), anti-diagonal (`row - col`) and column.
+        assert!(!verify(&[1, 3, 2, 0]));
+        assert!(!verify(&[0, 3, 1, 2]));
+        assert!(!verify(&[1, 3, 3, 0]));
+        // A column out of the board.
+        assert!(!verify(&[0, 5]));
+    }
+}
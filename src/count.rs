@@ -0,0 +1,234 @@
+//! Counting all the solutions of N-Queens with bitmasks.
+//!
+//! Each row is searched with three masks holding the columns, diagonals and anti-diagonals which
+//! are already under attack. Shifting the diagonal masks by one moves them to the next row, so
+//! the free squares of a row are found with a handful of bit operations.
+//!
+//! The results match the OEIS A000170 sequence: 1, 0, 0, 2, 10, 4, 40, 92, 352, 724, ...
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// The largest board which fits in the masks.
+pub const MAX_N: usize = 64;
+
+/// Count the ways of placing the queens on the remaining rows.
+///
+/// `full` has the lowest `n` bits set, one per column. `cols`, `diags` and `anti_diags` are the
+/// squares of the current row which are attacked vertically and by each diagonal.
+///
+/// The count of a single subtree fits in a `u64` for any board which can be searched in practice.
+pub(crate) fn count_masks(full: u64, cols: u64, diags: u64, anti_diags: u64) -> u64 {
+    if cols == full {
+        return 1;
+    }
+    let mut total = 0;
+    let mut free = full & !(cols | diags | anti_diags);
+    while free != 0 {
+        let bit = free & free.wrapping_neg(); // lowest free square
+        free ^= bit;
+        total += count_masks(
+            full,
+            cols | bit,
+            ((diags | bit) << 1) & full,
+            (anti_diags | bit) >> 1,
+        );
+    }
+    total
+}
+
+/// The mask with one bit set for each of the `n` columns.
+pub(crate) fn full_mask(n: usize) -> u64 {
+    if n == MAX_N {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+/// Count every solution for `n` queens on an `n*n` board.
+///
+/// Only the queens of the first row on the left half of the board are searched, the rest are
+/// their mirror images.
+///
+/// # Caveats
+/// - Panics if `n` is larger than `MAX_N`.
+pub fn count(n: usize) -> u128 {
+    assert!(n <= MAX_N, "Can not count boards larger than {}", MAX_N);
+    if n == 0 {
+        return 1;
+    }
+    let full = full_mask(n);
+    let mut total: u128 = 0;
+    for col in 0..(n / 2) {
+        let bit = 1 << col;
+        total += count_masks(full, bit, (bit << 1) & full, bit >> 1) as u128;
+    }
+    total *= 2;
+    if n % 2 == 1 {
+        let bit = 1 << (n / 2);
+        total += count_masks(full, bit, (bit << 1) & full, bit >> 1) as u128;
+    }
+    total
+}
+
+/// A subtree of the search, to be counted on its own.
+#[derive(Debug, Clone)]
+struct Task {
+    /// How many times the subtree is counted (2 if its mirror image is skipped).
+    weight: u128,
+    cols: u64,
+    diags: u64,
+    anti_diags: u64,
+}
+
+/// Split the search into the subtrees after placing the queens of the first `depth` rows.
+fn split(n: usize, depth: usize) -> Vec<Task> {
+    let full = full_mask(n);
+    // Like `count`, only the left half of the first row is searched.
+    let mut tasks = (0..((n + 1) / 2))
+        .map(|col| {
+            let bit = 1 << col;
+            Task {
+                weight: if n % 2 == 1 && col == n / 2 { 1 } else { 2 },
+                cols: bit,
+                diags: (bit << 1) & full,
+                anti_diags: bit >> 1,
+            }
+        })
+        .collect::<Vec<Task>>();
+    for _ in 1..depth.min(n) {
+        let mut next = vec![];
+        for task in tasks {
+            let mut free = full & !(task.cols | task.diags | task.anti_diags);
+            while free != 0 {
+                let bit = free & free.wrapping_neg();
+                free ^= bit;
+                next.push(Task {
+                    weight: task.weight,
+                    cols: task.cols | bit,
+                    diags: ((task.diags | bit) << 1) & full,
+                    anti_diags: (task.anti_diags | bit) >> 1,
+                });
+            }
+        }
+        tasks = next;
+    }
+    tasks
+}
+
+/// Count every solution like `count`, on several threads.
+///
+/// The placements of the first `depth` rows split the search into independent tasks which the
+/// `threads` workers take one at a time. The total does not depend on the order they finish in.
+///
+/// # Caveats
+/// - Panics if `n` is larger than `MAX_N`.
+pub fn count_parallel(n: usize, threads: usize, depth: usize) -> u128 {
+    assert!(n <= MAX_N, "Can not count boards larger than {}", MAX_N);
+    if n == 0 {
+        return 1;
+    }
+    let full = full_mask(n);
+    let tasks = split(n, depth.max(1));
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut total = 0;
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let task = match tasks.get(i) {
+                            Some(task) => task,
+                            None => return total,
+                        };
+                        let count = count_masks(full, task.cols, task.diags, task.anti_diags);
+                        total += task.weight * count as u128;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .map(|w| w.join().expect("A counting worker panicked"))
+            .sum()
+    })
+}
+
+/// Call `f` with every solution for `n` queens on an `n*n` board.
+///
+/// Each solution is given as the column of the queen on each row.
+///
+/// # Caveats
+/// - Panics if `n` is larger than `MAX_N`.
+pub fn for_each_solution<F: FnMut(&[usize])>(n: usize, mut f: F) {
+    assert!(n <= MAX_N, "Can not enumerate boards larger than {}", MAX_N);
+    let mut cols = Vec::with_capacity(n);
+    visit_masks(full_mask(n), 0, 0, 0, &mut cols, &mut f);
+}
+
+/// The same search as `count_masks` which also keeps the path to each solution.
+fn visit_masks<F: FnMut(&[usize])>(
+    full: u64,
+    cols: u64,
+    diags: u64,
+    anti_diags: u64,
+    path: &mut Vec<usize>,
+    f: &mut F,
+) {
+    if cols == full {
+        f(path);
+        return;
+    }
+    let mut free = full & !(cols | diags | anti_diags);
+    while free != 0 {
+        let bit = free & free.wrapping_neg();
+        free ^= bit;
+        path.push(bit.trailing_zeros() as usize);
+        visit_masks(
+            full,
+            cols | bit,
+            ((diags | bit) << 1) & full,
+            (anti_diags | bit) >> 1,
+            path,
+            f,
+        );
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// OEIS A000170 for n = 1..=12.
+    const KNOWN: [u128; 12] = [1, 0, 0, 2, 10, 4, 40, 92, 352, 724, 2680, 14200];
+
+    #[test]
+    fn count_matches_oeis() {
+        for (i, &expected) in KNOWN.iter().enumerate() {
+            assert_eq!(count(i + 1), expected, "n = {}", i + 1);
+        }
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        // 16 threads is more than the first-row columns of every board here.
+        for n in [1, 4, 6, 8, 9, 10] {
+            for threads in [1, 2, 3, 16] {
+                for depth in [1, 2, 3] {
+                    assert_eq!(
+                        count_parallel(n, threads, depth),
+                        count(n),
+                        "n = {}, threads = {}, depth = {}",
+                        n,
+                        threads,
+                        depth
+                    );
+                }
+            }
+        }
+    }
+}
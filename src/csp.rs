@@ -0,0 +1,207 @@
+//! N-Queens as a constraint satisfaction problem.
+//!
+//! Each row is a variable whose domain is the columns its queen may still take. After each
+//! assignment the domains of the other rows are pruned (forward checking or AC-3), the next row is
+//! the one with the minimum remaining values (MRV) and its columns are tried starting from the
+//! least constraining value (LCV).
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Board, Error};
+
+/// How the domains are pruned after an assignment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inference {
+    /// No pruning, only the assigned rows are checked (plain backtracking).
+    None,
+    /// Remove the values conflicting with the new assignment from the unassigned rows.
+    ForwardChecking,
+    /// Make every pair of rows arc consistent (AC-3).
+    Ac3,
+}
+
+impl FromStr for Inference {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "forward-checking" => Ok(Self::ForwardChecking),
+            "ac3" => Ok(Self::Ac3),
+            _ => Err(Error::UnknownInference(s.to_string())),
+        }
+    }
+}
+
+impl Display for Inference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::None => "none",
+            Self::ForwardChecking => "forward-checking",
+            Self::Ac3 => "ac3",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The parameters of the search.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub inference: Inference,
+    /// Choose the row with the fewest columns left instead of the first unassigned one.
+    pub mrv: bool,
+    /// Try the columns ruling out the fewest values of other rows first.
+    pub lcv: bool,
+}
+
+/// The outcome of the search.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The solution (if one exists).
+    pub board: Option<Board>,
+    /// The number of assignments tried.
+    pub nodes: usize,
+    /// The number of assignments undone.
+    pub backtracks: usize,
+}
+
+/// Can the queens of two rows be on these columns together.
+fn compatible(row1: usize, col1: usize, row2: usize, col2: usize) -> bool {
+    col1 != col2 && col1.abs_diff(col2) != row1.abs_diff(row2)
+}
+
+/// The state of the search.
+struct Search<'a> {
+    options: &'a Options,
+    /// The columns each row may still take.
+    domains: Vec<Vec<usize>>,
+    /// The column of each assigned row.
+    assignment: Vec<Option<usize>>,
+    nodes: usize,
+    backtracks: usize,
+}
+
+impl<'a> Search<'a> {
+    fn select_row(&self) -> Option<usize> {
+        let unassigned = (0..self.domains.len()).filter(|&r| self.assignment[r].is_none());
+        if self.options.mrv {
+            unassigned.min_by_key(|&r| self.domains[r].len())
+        } else {
+            unassigned.min()
+        }
+    }
+
+    /// The number of values of the unassigned rows which an assignment rules out.
+    fn ruled_out(&self, row: usize, col: usize) -> usize {
+        (0..self.domains.len())
+            .filter(|&r| r != row && self.assignment[r].is_none())
+            .map(|r| {
+                self.domains[r]
+                    .iter()
+                    .filter(|&&c| !compatible(row, col, r, c))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn consistent(&self, row: usize, col: usize) -> bool {
+        self.assignment
+            .iter()
+            .enumerate()
+            .all(|(r, c)| c.map_or(true, |c| compatible(row, col, r, c)))
+    }
+
+    /// Prune the domains after assigning a row. Returns false if a domain is wiped out.
+    fn infer(&mut self, row: usize, col: usize) -> bool {
+        match self.options.inference {
+            Inference::None => true,
+            Inference::ForwardChecking => {
+                for r in 0..self.domains.len() {
+                    if r != row && self.assignment[r].is_none() {
+                        self.domains[r].retain(|&c| compatible(row, col, r, c));
+                        if self.domains[r].is_empty() {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }
+            Inference::Ac3 => self.ac3(),
+        }
+    }
+
+    /// Remove the values of every row without a compatible value in some other row.
+    fn ac3(&mut self) -> bool {
+        let n = self.domains.len();
+        let mut queue = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .collect::<Vec<(usize, usize)>>();
+        while let Some((i, j)) = queue.pop() {
+            let before = self.domains[i].len();
+            let other = self.domains[j].clone();
+            self.domains[i].retain(|&ci| other.iter().any(|&cj| compatible(i, ci, j, cj)));
+            if self.domains[i].is_empty() {
+                return false;
+            }
+            if self.domains[i].len() != before {
+                queue.extend((0..n).filter(|&k| k != i && k != j).map(|k| (k, i)));
+            }
+        }
+        true
+    }
+
+    fn solve(&mut self) -> bool {
+        let row = match self.select_row() {
+            Some(row) => row,
+            None => return true,
+        };
+
+        let mut values = self.domains[row].clone();
+        if self.options.lcv {
+            values.sort_by_key(|&col| self.ruled_out(row, col));
+        }
+        for col in values {
+            if !self.consistent(row, col) {
+                continue;
+            }
+            self.nodes += 1;
+            let saved = self.domains.clone();
+            self.assignment[row] = Some(col);
+            self.domains[row] = vec![col];
+            if self.infer(row, col) && self.solve() {
+                return true;
+            }
+            self.backtracks += 1;
+            self.assignment[row] = None;
+            self.domains = saved;
+        }
+        false
+    }
+}
+
+/// Solve for `n` queens as a constraint satisfaction problem.
+pub fn solve(n: usize, options: &Options) -> Report {
+    let mut search = Search {
+        options,
+        domains: vec![(0..n).collect(); n],
+        assignment: vec![None; n],
+        nodes: 0,
+        backtracks: 0,
+    };
+    let board = if search.solve() {
+        let cols = search
+            .assignment
+            .iter()
+            .map(|c| c.expect("Every row is assigned"))
+            .collect::<Vec<usize>>();
+        Some(Board::from_permutation(&cols).expect("The columns are all different"))
+    } else {
+        None
+    };
+    Report {
+        board,
+        nodes: search.nodes,
+        backtracks: search.backtracks,
+    }
+}
@@ -0,0 +1,97 @@
+//! How different the boards of a population are, and fitness sharing to keep them different.
+//!
+//! The distance of two boards is the number of queens of one which are not on a square of the
+//! other, so the same placements (in any order) have a distance of 0.
+
+use crate::Board;
+
+impl Board {
+    /// The number of queens of this board on squares without a queen of the other board.
+    pub fn distance(&self, other: &Board) -> usize {
+        let (a, b) = (self.sorted_queens(), other.sorted_queens());
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        a.len() - shared
+    }
+}
+
+/// The spread of the distances between the boards of a population.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diversity {
+    /// The mean distance of the pairs of boards.
+    pub mean_distance: f64,
+    /// The distance of the closest pair of boards.
+    pub min_distance: usize,
+    /// The distance of the farthest pair of boards.
+    pub max_distance: usize,
+    /// The number of different placements.
+    pub unique: usize,
+}
+
+/// The diversity of the boards (all the distances are 0 with fewer than two boards).
+pub fn diversity(boards: &[Board]) -> Diversity {
+    let distances = distances(boards);
+    let pairs = distances
+        .iter()
+        .enumerate()
+        .flat_map(|(i, row)| row[(i + 1)..].iter().copied())
+        .collect::<Vec<usize>>();
+    let unique = (0..boards.len())
+        .filter(|&i| (0..i).all(|j| distances[i][j] != 0))
+        .count();
+    Diversity {
+        mean_distance: if pairs.is_empty() {
+            0.0
+        } else {
+            pairs.iter().sum::<usize>() as f64 / pairs.len() as f64
+        },
+        min_distance: pairs.iter().copied().min().unwrap_or(0),
+        max_distance: pairs.iter().copied().max().unwrap_or(0),
+        unique,
+    }
+}
+
+/// The costs (`checks_count`) of the boards after fitness sharing.
+///
+/// Each board shares its fitness `1 / (1 + cost)` with the boards closer than `radius`, weighted
+/// by `1 - distance / radius`, so the boards of a crowded niche look less fit than a lone board
+/// of the same cost. The shared fitness is turned back into a cost.
+pub fn shared_costs(boards: &[Board], radius: usize) -> Vec<f64> {
+    let distances = distances(boards);
+    boards
+        .iter()
+        .enumerate()
+        .map(|(i, board)| {
+            let niche = distances[i]
+                .iter()
+                .filter(|&&d| d < radius)
+                .map(|&d| 1.0 - d as f64 / radius as f64)
+                .sum::<f64>()
+                // A board is always in its own niche.
+                .max(1.0);
+            (1.0 + board.checks_count() as f64) * niche - 1.0
+        })
+        .collect()
+}
+
+/// The distances of every pair of boards.
+fn distances(boards: &[Board]) -> Vec<Vec<usize>> {
+    let mut v = vec![vec![0; boards.len()]; boards.len()];
+    for i in 0..boards.len() {
+        for j in (i + 1)..boards.len() {
+            v[i][j] = boards[i].distance(&boards[j]);
+            v[j][i] = v[i][j];
+        }
+    }
+    v
+}
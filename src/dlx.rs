@@ -0,0 +1,309 @@
+//! Knuth's Dancing Links (Algorithm X) over the exact cover form of N-Queens.
+//!
+//! Each square is an option covering its row, column, diagonal and anti-diagonal. The rows and
+//! columns are primary items (covered exactly once) while the diagonals are secondary items
+//! (covered at most once). Queens placed beforehand are covered before the search starts and
+//! blocked squares are left out of the options.
+
+use crate::{Board, Error, Point};
+
+/// The exact cover matrix as circular doubly linked lists stored in arrays.
+///
+/// Node 0 is the root, the nodes `1..=items` are the item headers and the rest are the options'
+/// nodes (4 per option).
+struct Links {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The item header of each node.
+    item: Vec<usize>,
+    /// The number of options left in each item (indexed by the header).
+    size: Vec<usize>,
+    /// The square of each option node (headers have a placeholder).
+    square: Vec<Point>,
+}
+
+impl Links {
+    fn new(primary: usize, secondary: usize) -> Self {
+        let headers = 1 + primary + secondary;
+        let mut links = Self {
+            left: (0..headers).collect(),
+            right: (0..headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            item: (0..headers).collect(),
+            size: vec![0; headers],
+            square: vec![Point::new(0, 0); headers],
+        };
+        // Only the primary items are on the root's list, the secondary ones are never chosen.
+        for i in 0..=primary {
+            links.left[i] = if i == 0 { primary } else { i - 1 };
+            links.right[i] = if i == primary { 0 } else { i + 1 };
+        }
+        links
+    }
+
+    /// Add an option covering the given items.
+    fn add_option(&mut self, square: Point, items: &[usize]) {
+        let first = self.left.len();
+        for (k, &item) in items.iter().enumerate() {
+            let node = first + k;
+            self.left.push(if k == 0 {
+                first + items.len() - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if k == items.len() - 1 {
+                first
+            } else {
+                node + 1
+            });
+            self.up.push(self.up[item]);
+            self.down.push(item);
+            let last = self.up[item];
+            self.down[last] = node;
+            self.up[item] = node;
+            self.item.push(item);
+            self.size[item] += 1;
+            self.square.push(square.clone());
+        }
+    }
+
+    fn cover(&mut self, item: usize) {
+        let (l, r) = (self.left[item], self.right[item]);
+        self.right[l] = r;
+        self.left[r] = l;
+        let mut i = self.down[item];
+        while i != item {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.item[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, item: usize) {
+        let mut i = self.up[item];
+        while i != item {
+            let mut j = self.left[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                self.size[self.item[j]] += 1;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (l, r) = (self.left[item], self.right[item]);
+        self.right[l] = item;
+        self.left[r] = item;
+    }
+
+    /// Algorithm X: call `f` with the chosen nodes of every cover, stop once `f` returns false.
+    ///
+    /// Returns false if the search was stopped.
+    fn search<F: FnMut(&[usize]) -> bool>(&mut self, chosen: &mut Vec<usize>, f: &mut F) -> bool {
+        if self.right[0] == 0 {
+            return f(chosen);
+        }
+        // Choose the item with the fewest options left.
+        let mut item = self.right[0];
+        let mut i = self.right[item];
+        while i != 0 {
+            if self.size[i] < self.size[item] {
+                item = i;
+            }
+            i = self.right[i];
+        }
+
+        self.cover(item);
+        let mut keep_going = true;
+        let mut r = self.down[item];
+        while r != item && keep_going {
+            chosen.push(r);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.item[j]);
+                j = self.right[j];
+            }
+            keep_going = self.search(chosen, f);
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.item[j]);
+                j = self.left[j];
+            }
+            chosen.pop();
+            r = self.down[r];
+        }
+        self.uncover(item);
+        keep_going
+    }
+}
+
+/// An N-Queens instance with optional placed queens and blocked squares.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    n: usize,
+    placed: Vec<Point>,
+    blocked: Vec<Point>,
+}
+
+impl Problem {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            placed: vec![],
+            blocked: vec![],
+        }
+    }
+
+    /// Require a queen on a square.
+    pub fn place(mut self, point: Point) -> Self {
+        self.placed.push(point);
+        self
+    }
+
+    /// Forbid placing a queen on a square.
+    pub fn block(mut self, point: Point) -> Self {
+        self.blocked.push(point);
+        self
+    }
+
+    /// Build the links with the placed queens already covered.
+    fn links(&self) -> Result<Links, Error> {
+        let n = self.n;
+        let diags = (2 * n).saturating_sub(1);
+        let mut links = Links::new(2 * n, 2 * diags);
+        for row in 0..n {
+            for col in 0..n {
+                let point = Point::new(row, col);
+                if self.blocked.contains(&point) {
+                    continue;
+                }
+                links.add_option(
+                    point,
+                    &[
+                        1 + row,
+                        1 + n + col,
+                        1 + 2 * n + row + col,
+                        1 + 2 * n + diags + row + n - 1 - col,
+                    ],
+                );
+            }
+        }
+
+        let mut covered = vec![false; links.size.len()];
+        for point in &self.placed {
+            if point.row >= n || point.col >= n {
+                return Err(Error::OutOfBounds(point.clone()));
+            }
+            let option = (links.size.len()..links.square.len())
+                .step_by(4)
+                .find(|&i| links.square[i] == *point)
+                .ok_or_else(|| Error::BlockedSquare(point.clone()))?;
+            for node in option..(option + 4) {
+                let item = links.item[node];
+                if covered[item] {
+                    return Err(Error::ConflictingConstraints(point.clone()));
+                }
+                covered[item] = true;
+                links.cover(item);
+            }
+        }
+        Ok(links)
+    }
+
+    /// Call `f` with every solution, stop once `f` returns false.
+    pub fn for_each_solution<F: FnMut(Board) -> bool>(&self, mut f: F) -> Result<(), Error> {
+        let mut links = self.links()?;
+        let square = links.square.clone();
+        links.search(&mut vec![], &mut |chosen| {
+            let queens = self
+                .placed
+                .iter()
+                .cloned()
+                .chain(chosen.iter().map(|&node| square[node].clone()))
+                .collect();
+            f(Board::with_queens(self.n, queens).expect("An exact cover has distinct squares"))
+        });
+        Ok(())
+    }
+
+    /// Find up to `limit` solutions.
+    pub fn solutions(&self, limit: usize) -> Result<Vec<Board>, Error> {
+        let mut v = vec![];
+        if limit > 0 {
+            self.for_each_solution(|board| {
+                v.push(board);
+                v.len() < limit
+            })?;
+        }
+        Ok(v)
+    }
+
+    /// Count every solution.
+    pub fn count(&self) -> Result<u128, Error> {
+        let mut links = self.links()?;
+        let mut total = 0;
+        links.search(&mut vec![], &mut |_| {
+            total += 1;
+            true
+        });
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_with_constraints() {
+        let corner = || Point::new(0, 0);
+        assert_eq!(Problem::new(8).count(), Ok(92));
+        assert_eq!(Problem::new(8).place(corner()).count(), Ok(4));
+        assert_eq!(Problem::new(8).block(corner()).count(), Ok(88));
+    }
+
+    #[test]
+    fn solutions_keep_the_placed_queens() {
+        let point = Point::new(2, 5);
+        let solutions = Problem::new(8).place(point.clone()).solutions(100).unwrap();
+        assert!(!solutions.is_empty());
+        for board in solutions {
+            assert!(board.index_of(&point).is_some());
+            assert_eq!(board.queens().len(), 8);
+            assert_eq!(board.checks_count(), 0);
+        }
+    }
+
+    #[test]
+    fn rejects_contradicting_constraints() {
+        let corner = Point::new(0, 0);
+        assert_eq!(
+            Problem::new(8)
+                .place(corner.clone())
+                .block(corner.clone())
+                .count(),
+            Err(Error::BlockedSquare(corner))
+        );
+        assert_eq!(
+            Problem::new(8)
+                .place(Point::new(0, 0))
+                .place(Point::new(3, 3))
+                .count(),
+            Err(Error::ConflictingConstraints(Point::new(3, 3)))
+        );
+        assert_eq!(
+            Problem::new(8).place(Point::new(8, 0)).count(),
+            Err(Error::OutOfBounds(Point::new(8, 0)))
+        );
+    }
+}
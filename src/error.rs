@@ -0,0 +1,164 @@
+//! The errors of the library.
+
+use std::fmt::Display;
+
+use crate::Point;
+
+/// Everything that can go wrong in the library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A queen is already on the square.
+    SquareOccupied(Point),
+    /// There is no queen on the square.
+    NoQueenAt(Point),
+    /// The square is not on the board.
+    OutOfBounds(Point),
+    /// There is no free square left on the board.
+    BoardFull,
+    /// There is no queen on the board.
+    NoQueens,
+    /// The heuristic could not be lowered by a move.
+    LocalMinimum,
+    /// The search ran out of steps before solving the problem.
+    Unsolved,
+    /// The problem has no solution.
+    Unsatisfiable,
+    /// No board of the genetic population was left.
+    Extinct,
+    /// A queen is placed on a square which is also blocked.
+    BlockedSquare(Point),
+    /// A placed queen is checked by another placed queen.
+    ConflictingConstraints(Point),
+    /// A child of the genetic algorithm can not take its queens from this many parents.
+    TooManyParents { parents: usize, n: usize },
+    /// A child of the genetic algorithm needs at least one parent.
+    NoParents,
+    /// The survivors of a generation are not between one and the population.
+    InvalidSurvivors { survivors: usize, population: usize },
+    /// A beam search needs at least one board.
+    EmptyBeam,
+    /// Not a point like `3x5`.
+    BadPoint(String),
+    /// Not a DIMACS literal.
+    BadLiteral(String),
+    /// A literal of a SAT model whose variable is not a square of the board.
+    VariableOutOfBounds(i32),
+    /// A part of the answer of an external tool which could not be read.
+    BadAnswer(String),
+    /// The answer of MiniZinc does not assign `q`.
+    MissingAssignment,
+    /// A file could not be read or written.
+    Io { path: String, reason: String },
+    /// The name is not a known `export::Format`.
+    UnknownFormat(String),
+    /// The name is not a known `csp::Inference`.
+    UnknownInference(String),
+    /// The name is not a known `anneal::Schedule`.
+    UnknownSchedule(String),
+    /// The name is not a known `genetic::Topology`.
+    UnknownTopology(String),
+    /// The name is not a known `genetic::Crossover`.
+    UnknownCrossover(String),
+    /// The name is not a known `permutation::Crossover`.
+    UnknownPermutationCrossover(String),
+    /// The name is not a known `mutation::Mutation`.
+    UnknownMutation(String),
+    /// The name is not a known strategy (see `selection::parse`).
+    UnknownSelection(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SquareOccupied(p) => write!(f, "The point {} is already taken.", p),
+            Self::NoQueenAt(p) => write!(f, "There is no queen on {}.", p),
+            Self::OutOfBounds(p) => write!(f, "The point {} is out of the board.", p),
+            Self::BoardFull => write!(
+                f,
+                "Could not place more queens on the board (is it filled?)"
+            ),
+            Self::NoQueens => write!(f, "There is no queen on the board."),
+            Self::LocalMinimum => write!(f, "Got stuck in a local minima"),
+            Self::Unsolved => write!(f, "Could not remove all the conflicts in the given steps"),
+            Self::Unsatisfiable => write!(f, "The problem has no solution."),
+            Self::Extinct => write!(f, "Everybody died!"),
+            Self::BlockedSquare(p) => {
+                write!(f, "The queen on {} is placed on a blocked square.", p)
+            }
+            Self::ConflictingConstraints(p) => {
+                write!(f, "The queen placed on {} checks another placed queen.", p)
+            }
+            Self::TooManyParents { parents, n } => write!(
+                f,
+                "The chess board size ({}) is not larger than the number of parents ({}). \
+                 To keep the code simple, this is not supported.",
+                n, parents
+            ),
+            Self::NoParents => write!(f, "A child needs at least one parent."),
+            Self::InvalidSurvivors {
+                survivors,
+                population,
+            } => write!(
+                f,
+                "The survivors ({}) must be between one and the population ({}).",
+                survivors, population
+            ),
+            Self::EmptyBeam => write!(f, "The beam needs at least one board."),
+            Self::BadPoint(s) => write!(
+                f,
+                "Expected a point like 3x5 (row and column counting from 1), got '{}'",
+                s
+            ),
+            Self::BadLiteral(s) => write!(f, "Expected a literal in the model, got '{}'", s),
+            Self::VariableOutOfBounds(lit) => {
+                write!(f, "The literal {} of the model is out of the board.", lit)
+            }
+            Self::BadAnswer(s) => write!(f, "Could not read '{}' in the answer of the tool.", s),
+            Self::MissingAssignment => write!(f, "The answer does not assign q."),
+            Self::Io { path, reason } => write!(f, "Could not access {}: {}", path, reason),
+            Self::UnknownFormat(s) => {
+                write!(f, "Unknown format '{}', expected one of mzn, smt2 or lp", s)
+            }
+            Self::UnknownInference(s) => write!(
+                f,
+                "Unknown inference '{}', expected one of none, forward-checking or ac3",
+                s
+            ),
+            Self::UnknownSchedule(s) => write!(
+                f,
+                "Unknown schedule '{}', expected one of exponential, linear or logarithmic",
+                s
+            ),
+            Self::UnknownTopology(s) => {
+                write!(f, "Unknown topology '{}', expected one of ring or full", s)
+            }
+            Self::UnknownCrossover(s) => write!(
+                f,
+                "Unknown crossover '{}', expected one of split, k-point[:K], uniform or \
+                 conflict-aware",
+                s
+            ),
+            Self::UnknownPermutationCrossover(s) => {
+                write!(
+                    f,
+                    "Unknown crossover '{}', expected one of pmx, ox or cx",
+                    s
+                )
+            }
+            Self::UnknownMutation(s) => write!(
+                f,
+                "Unknown mutation '{}', expected one of reset, swap, inversion, scramble or \
+                 min-conflicts",
+                s
+            ),
+            Self::UnknownSelection(s) => write!(
+                f,
+                "Unknown selection '{}', expected one of truncation, tournament[:SIZE], \
+                 roulette, rank or sus",
+                s
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
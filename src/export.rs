@@ -0,0 +1,232 @@
+//! Writing the N-Queens model for external constraint tools and reading their answers back.
+//!
+//! MiniZinc and SMT-LIB use the queen's column on each row (`q[i]` and `q_i`, counting from 1),
+//! while the LP model has a binary variable `x_ROW_COL` for each square.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Board, Error, Point};
+
+/// The formats of the external tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A MiniZinc model (`.mzn`).
+    MiniZinc,
+    /// An SMT-LIB 2 script over integer arithmetic (`.smt2`).
+    SmtLib,
+    /// A CPLEX LP file with binary variables (`.lp`).
+    Lp,
+}
+
+impl Format {
+    /// The usual file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::MiniZinc => "mzn",
+            Self::SmtLib => "smt2",
+            Self::Lp => "lp",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mzn" | "minizinc" => Ok(Self::MiniZinc),
+            "smt2" | "smtlib" => Ok(Self::SmtLib),
+            "lp" => Ok(Self::Lp),
+            _ => Err(Error::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// Write the model of `n` queens with the `placed` queens fixed.
+pub fn write(format: Format, n: usize, placed: &[Point]) -> Result<String, Error> {
+    if let Some(p) = placed.iter().find(|p| p.row >= n || p.col >= n) {
+        return Err(Error::OutOfBounds(p.clone()));
+    }
+    Ok(match format {
+        Format::MiniZinc => minizinc(n, placed),
+        Format::SmtLib => smtlib(n, placed),
+        Format::Lp => lp(n, placed),
+    })
+}
+
+fn minizinc(n: usize, placed: &[Point]) -> String {
+    let mut s = String::new();
+    s += "include \"alldifferent.mzn\";\n\n";
+    s += &format!("int: n = {};\n", n);
+    s += "% The column of the queen on each row.\n";
+    s += "array[1..n] of var 1..n: q;\n\n";
+    s += "constraint alldifferent(q);\n";
+    s += "constraint alldifferent(i in 1..n)(q[i] + i);\n";
+    s += "constraint alldifferent(i in 1..n)(q[i] - i);\n";
+    for p in placed {
+        s += &format!("constraint q[{}] = {};\n", p.row + 1, p.col + 1);
+    }
+    s += "\nsolve satisfy;\n";
+    s += "output [\"q = \\(q);\\n\"];\n";
+    s
+}
+
+fn smtlib(n: usize, placed: &[Point]) -> String {
+    let q = (1..=n).map(|i| format!("q_{}", i)).collect::<Vec<String>>();
+    let shifted = |op: &str| {
+        (1..=n)
+            .map(|i| format!("({} q_{} {})", op, i, i))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    let mut s = String::new();
+    s += "(set-logic QF_LIA)\n";
+    s += "; The column of the queen on each row.\n";
+    for name in &q {
+        s += &format!("(declare-const {} Int)\n", name);
+        s += &format!("(assert (and (>= {0} 1) (<= {0} {1})))\n", name, n);
+    }
+    if n > 1 {
+        s += &format!("(assert (distinct {}))\n", q.join(" "));
+        s += &format!("(assert (distinct {}))\n", shifted("+"));
+        s += &format!("(assert (distinct {}))\n", shifted("-"));
+    }
+    for p in placed {
+        s += &format!("(assert (= q_{} {}))\n", p.row + 1, p.col + 1);
+    }
+    s += "(check-sat)\n";
+    s += "(get-model)\n";
+    s
+}
+
+fn lp(n: usize, placed: &[Point]) -> String {
+    let x = |row: usize, col: usize| format!("x_{}_{}", row + 1, col + 1);
+    let sum = |points: Vec<(usize, usize)>| {
+        points
+            .into_iter()
+            .map(|(row, col)| x(row, col))
+            .collect::<Vec<String>>()
+            .join(" + ")
+    };
+    let all = (0..n).flat_map(|row| (0..n).map(move |col| (row, col)));
+
+    let mut s = String::new();
+    s += "\\ x_ROW_COL is 1 if the square has a queen.\n";
+    s += "Maximize\n";
+    s += &format!(" queens: {}\n", sum(all.clone().collect()));
+    s += "Subject To\n";
+    for i in 0..n {
+        s += &format!(
+            " row_{}: {} = 1\n",
+            i + 1,
+            sum((0..n).map(|c| (i, c)).collect())
+        );
+        s += &format!(
+            " col_{}: {} = 1\n",
+            i + 1,
+            sum((0..n).map(|r| (r, i)).collect())
+        );
+    }
+    for d in 0..(2 * n).saturating_sub(1) {
+        let diag = all
+            .clone()
+            .filter(|(row, col)| row + col == d)
+            .collect::<Vec<_>>();
+        let anti_diag = all
+            .clone()
+            .filter(|(row, col)| row + n - 1 - col == d)
+            .collect::<Vec<_>>();
+        if diag.len() > 1 {
+            s += &format!(" diag_{}: {} <= 1\n", d + 1, sum(diag));
+            s += &format!(" anti_diag_{}: {} <= 1\n", d + 1, sum(anti_diag));
+        }
+    }
+    for p in placed {
+        s += &format!(" placed_{}: {} = 1\n", x(p.row, p.col), x(p.row, p.col));
+    }
+    s += "Binary\n";
+    for (row, col) in all {
+        s += &format!(" {}\n", x(row, col));
+    }
+    s += "End\n";
+    s
+}
+
+/// Split an answer into its words, dropping the punctuation of the formats.
+fn tokens(s: &str) -> Vec<&str> {
+    s.split(|c: char| c.is_whitespace() || "()[],;=:".contains(c))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// The number following a variable name (within a few words, like `q_3 () Int 5`).
+fn value_after(tokens: &[&str], i: usize) -> Option<f64> {
+    tokens
+        .iter()
+        .skip(i + 1)
+        .take(3)
+        .find_map(|t| t.parse::<f64>().ok())
+}
+
+fn bad_answer(token: &str) -> Error {
+    Error::BadAnswer(token.to_string())
+}
+
+/// Read the answer of an external tool to the model of `n` queens as a `Board`.
+///
+/// - MiniZinc: the output of the model (`q = [2, 4, 1, 3];`).
+/// - SMT-LIB: the answer to `(get-model)` or `(get-value ...)`.
+/// - LP: the variables and their values, one per line (`x_1_2 1`), like most LP solvers print.
+pub fn import(format: Format, n: usize, s: &str) -> Result<Board, Error> {
+    let tokens = tokens(s);
+    let mut queens = vec![];
+    match format {
+        Format::MiniZinc => {
+            let start = tokens
+                .iter()
+                .position(|t| *t == "q")
+                .ok_or(Error::MissingAssignment)?;
+            for (row, t) in tokens[(start + 1)..].iter().take(n).enumerate() {
+                let col = t.parse::<usize>().map_err(|_| bad_answer(t))?;
+                queens.push(Point::new(row + 1, col));
+            }
+        }
+        Format::SmtLib => {
+            for (i, t) in tokens.iter().enumerate() {
+                if let Some(row) = t.strip_prefix("q_") {
+                    let row = row.parse::<usize>().map_err(|_| bad_answer(t))?;
+                    let col = value_after(&tokens, i).ok_or_else(|| bad_answer(t))?;
+                    queens.push(Point::new(row, col as usize));
+                }
+            }
+        }
+        Format::Lp => {
+            for (i, t) in tokens.iter().enumerate() {
+                if let Some((row, col)) = t.strip_prefix("x_").and_then(|t| t.split_once('_')) {
+                    let row = row.parse::<usize>().map_err(|_| bad_answer(t))?;
+                    let col = col.parse::<usize>().map_err(|_| bad_answer(t))?;
+                    if value_after(&tokens, i).ok_or_else(|| bad_answer(t))? > 0.5 {
+                        queens.push(Point::new(row, col));
+                    }
+                }
+            }
+        }
+    }
+
+    // The tools count from 1.
+    let queens = queens
+        .into_iter()
+        .map(|p| match (p.row.checked_sub(1), p.col.checked_sub(1)) {
+            (Some(row), Some(col)) => Ok(Point::new(row, col)),
+            _ => Err(Error::BadAnswer(format!("{}x{}", p.row, p.col))),
+        })
+        .collect::<Result<Vec<Point>, Error>>()?;
+    Board::with_queens(n, queens)
+}
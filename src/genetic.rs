@@ -0,0 +1,816 @@
+//! The building blocks of the genetic algorithm.
+//!
+//! Each board is an individual and its queens are the genes. Every generation, the boards make a
+//! few greedy moves (`Board::lower_heuristic`), the fittest survive and their children (made of
+//! the genes of several parents and mutated) make the next generation.
+
+use rand::prelude::*;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::diversity::{self, Diversity};
+use crate::mutation::{AdaptiveRate, Mutation};
+use crate::selection::Selection;
+use crate::{Board, Error, Point};
+
+/// The growth of the adaptive mutation chance in each generation without progress.
+const ADAPTIVE_STEP: usize = 5;
+/// The highest adaptive mutation chance (unless the initial one is higher).
+const ADAPTIVE_MAX: usize = 50;
+
+/// Where the migrants of an island go in the island model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// To the next island, the last one sending to the first.
+    Ring,
+    /// To every other island.
+    Full,
+}
+
+impl FromStr for Topology {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ring" => Ok(Self::Ring),
+            "full" => Ok(Self::Full),
+            _ => Err(Error::UnknownTopology(s.to_string())),
+        }
+    }
+}
+
+impl Display for Topology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Ring => "ring",
+            Self::Full => "full",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The ways the queens of several parents are combined into a child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossover {
+    /// Contiguous slices of `n / parents` genes from each parent, the last one giving the rest
+    /// (the original crossover).
+    Split,
+    /// Cut the genes at `k` random points and take the pieces from the parents in turn.
+    KPoint(usize),
+    /// Take each gene from a random parent.
+    Uniform,
+    /// Take the least-threatened queens of each parent (by their `check_data`), skipping the
+    /// squares which are already taken when possible.
+    ConflictAware,
+}
+
+impl Crossover {
+    /// Combine the genes of the parents (which all have the same number of queens).
+    pub fn apply<R: Rng>(&self, parents: &[Board], rng: &mut R) -> Vec<Point> {
+        match *self {
+            Self::Split => split(parents),
+            Self::KPoint(k) => k_point(parents, k, rng),
+            Self::Uniform => {
+                let n = parents[0].queens().len();
+                (0..n)
+                    .map(|i| parents[rng.gen_range(0..parents.len())].queens()[i].clone())
+                    .collect()
+            }
+            Self::ConflictAware => conflict_aware(parents),
+        }
+    }
+}
+
+impl FromStr for Crossover {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "split" => Ok(Self::Split),
+            None if s == "k-point" => Ok(Self::KPoint(2)),
+            None if s == "uniform" => Ok(Self::Uniform),
+            None if s == "conflict-aware" => Ok(Self::ConflictAware),
+            Some(("k-point", k)) => k
+                .parse()
+                .map(Self::KPoint)
+                .map_err(|_| Error::UnknownCrossover(s.to_string())),
+            _ => Err(Error::UnknownCrossover(s.to_string())),
+        }
+    }
+}
+
+impl Display for Crossover {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Split => write!(f, "split"),
+            Self::KPoint(k) => write!(f, "k-point:{}", k),
+            Self::Uniform => write!(f, "uniform"),
+            Self::ConflictAware => write!(f, "conflict-aware"),
+        }
+    }
+}
+
+/// The parameters of a population.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The size of the board and number of queens.
+    pub n: usize,
+    /// The initial population of the boards.
+    pub population: usize,
+    /// The number of parents that combine into a single child.
+    pub parents: usize,
+    /// The maximum number of suvivors moved to the next generation.
+    pub survivors: usize,
+    /// How the survivors are picked from the boards of a generation.
+    pub selection: Arc<dyn Selection>,
+    /// Share the fitness of the boards closer than this distance for the selection (see
+    /// `diversity::shared_costs`).
+    pub sharing_radius: Option<usize>,
+    /// How the genes of the parents are combined into a child.
+    pub crossover: Crossover,
+    /// The mutation percentage for each single gene.
+    pub mutation_chance: usize,
+    /// How a gene is mutated.
+    pub mutation: Mutation,
+    /// Move the queens of a child landing on a taken square to the least-conflicted free square
+    /// (the nearest one of those) instead of discarding the child, and breed a whole population
+    /// each generation (see `breed`).
+    pub repair: bool,
+    /// Raise the mutation chance while the fittest board does not get better and lower it back
+    /// once it does.
+    pub adaptive_mutation: bool,
+    /// The number of moves in each generation.
+    pub moves_in_generation: usize,
+}
+
+/// What happened in a generation.
+#[derive(Debug, Clone)]
+pub struct Generation {
+    /// The heuristics of the boards when the generation was born.
+    pub born: Vec<usize>,
+    /// The heuristics of the boards after their moves (sorted).
+    pub lived: Vec<usize>,
+    /// The heuristics of the survivors (sorted).
+    pub survivors: Vec<usize>,
+    /// How different the boards were after their moves.
+    pub diversity: Diversity,
+    /// The mutation percentage of the children of the survivors.
+    pub mutation_chance: usize,
+    /// The number of children with colliding queens which were repaired.
+    pub repaired: usize,
+    /// The number of children with colliding queens which were discarded.
+    pub discarded: usize,
+    /// The board with the fewest checks after the moves.
+    pub best: Board,
+    /// The board without any checks (if one was found).
+    pub fittest: Option<Board>,
+}
+
+/// Why the evolution of a population stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// A board without any checks was found.
+    Solved,
+    /// A board with at most the target checks was found.
+    Target,
+    /// The wall-clock limit was reached.
+    TimeLimit,
+    /// The fittest board did not get better for the given number of generations.
+    Stagnation(usize),
+    /// The maximum number of generations was reached.
+    Generations,
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved => write!(f, "a solution was found"),
+            Self::Target => write!(f, "the target fitness was reached"),
+            Self::TimeLimit => write!(f, "the time limit was reached"),
+            Self::Stagnation(k) => write!(f, "no improvement in {} generations", k),
+            Self::Generations => write!(f, "the maximum number of generations was reached"),
+        }
+    }
+}
+
+/// When to stop the evolution of a population.
+#[derive(Debug, Clone)]
+pub struct StopCriteria {
+    /// The maximum number of generations.
+    pub generations: usize,
+    /// Stop once a board has at most this many checks.
+    pub target: usize,
+    /// Stop after this much time.
+    pub time_limit: Option<Duration>,
+    /// Stop after this many generations without a better board.
+    pub stagnation: Option<usize>,
+}
+
+/// Follows the generations of a population to tell when to stop.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    criteria: StopCriteria,
+    started: Instant,
+    generations: usize,
+    best: Option<usize>,
+    stagnant: usize,
+}
+
+impl Progress {
+    /// Start the clock of the criteria.
+    pub fn new(criteria: StopCriteria) -> Self {
+        Self {
+            criteria,
+            started: Instant::now(),
+            generations: 0,
+            best: None,
+            stagnant: 0,
+        }
+    }
+
+    /// The number of generations since the fittest board got better.
+    pub fn stagnant(&self) -> usize {
+        self.stagnant
+    }
+
+    /// The time since the clock was started.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Start counting the stagnant generations again (after a restart).
+    pub fn reset_stagnation(&mut self) {
+        self.stagnant = 0;
+        self.best = None;
+    }
+
+    /// Record a generation and return the reason to stop after it (if any).
+    pub fn update(&mut self, generation: &Generation) -> Option<StopReason> {
+        self.generations += 1;
+        let fittest = generation.best.checks_count();
+        match self.best {
+            Some(best) if fittest >= best => self.stagnant += 1,
+            _ => {
+                self.best = Some(fittest);
+                self.stagnant = 0;
+            }
+        }
+
+        let criteria = &self.criteria;
+        if generation.fittest.is_some() {
+            Some(StopReason::Solved)
+        } else if fittest <= criteria.target {
+            Some(StopReason::Target)
+        } else if criteria
+            .time_limit
+            .map_or(false, |limit| self.started.elapsed() >= limit)
+        {
+            Some(StopReason::TimeLimit)
+        } else if criteria.stagnation.map_or(false, |k| self.stagnant >= k) {
+            Some(StopReason::Stagnation(self.stagnant))
+        } else if self.generations >= criteria.generations {
+            Some(StopReason::Generations)
+        } else {
+            None
+        }
+    }
+}
+
+/// The boards of a generation and how they evolve.
+#[derive(Debug, Clone)]
+pub struct Population {
+    config: Config,
+    /// Holds the boards.
+    env: Vec<Board>,
+    /// The boards of the last generation after their moves (fittest first), since `env` holds
+    /// their children which have not lived yet.
+    lived: Vec<Board>,
+    mutation_rate: AdaptiveRate,
+}
+
+impl Population {
+    /// Create the primitive/initial boards, the natives of the env.
+    pub fn new<R: Rng>(config: Config, rng: &mut R) -> Result<Self, Error> {
+        if config.parents >= config.n {
+            return Err(Error::TooManyParents {
+                parents: config.parents,
+                n: config.n,
+            });
+        }
+        if config.parents == 0 {
+            return Err(Error::NoParents);
+        }
+        if config.survivors == 0 || config.survivors > config.population {
+            return Err(Error::InvalidSurvivors {
+                survivors: config.survivors,
+                population: config.population,
+            });
+        }
+        let env = vec![Board::new(config.n); config.population]
+            .into_iter()
+            .map(|i| i.init_n_queens(rng))
+            .collect::<Result<Vec<Board>, Error>>()?;
+        let mutation_rate = AdaptiveRate::new(config.mutation_chance, ADAPTIVE_STEP, ADAPTIVE_MAX);
+        Ok(Self {
+            config,
+            env,
+            lived: vec![],
+            mutation_rate,
+        })
+    }
+
+    /// A getter for the config.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// A getter for the boards.
+    pub fn boards(&self) -> &Vec<Board> {
+        &self.env
+    }
+
+    /// The heuristics of all the boards in the env.
+    pub fn heuristics(&self) -> Vec<usize> {
+        all_heuristics(&self.env)
+    }
+
+    /// The `count` fittest boards of the env.
+    pub fn fittest(&self, count: usize) -> Vec<Board> {
+        let mut env = self.env.clone();
+        env.sort_by_key(|a| a.checks_count());
+        env.truncate(count);
+        env
+    }
+
+    /// Keep the `elite` fittest boards of the last generation and replace the rest with new random
+    /// boards.
+    pub fn cataclysm<R: Rng>(&mut self, elite: usize, rng: &mut R) -> Result<(), Error> {
+        let mut env = if self.lived.is_empty() {
+            self.fittest(elite)
+        } else {
+            self.lived.iter().take(elite).cloned().collect()
+        };
+        for _ in env.len()..self.config.population {
+            env.push(Board::new(self.config.n).init_n_queens(rng)?);
+        }
+        self.env = env;
+        Ok(())
+    }
+
+    /// Replace the least fit boards of the env with the given ones.
+    pub fn immigrate(&mut self, boards: Vec<Board>) {
+        self.env.sort_by_key(|a| a.checks_count());
+        let kept = self.env.len().saturating_sub(boards.len());
+        self.env.truncate(kept);
+        self.env.extend(boards);
+    }
+
+    /// Let a generation live, pick the survivors and replace the env with their children.
+    ///
+    /// Fails if fewer boards than the survivors are left.
+    pub fn evolve<R: Rng>(&mut self, rng: &mut R) -> Result<Generation, Error> {
+        let config = &self.config;
+        let born = all_heuristics(&self.env);
+
+        // Let them live their lives
+        for board in self.env.iter_mut() {
+            for _ in 0..config.moves_in_generation {
+                let _ = board.lower_heuristic(rng);
+            }
+        }
+
+        // Sort by fitness
+        self.env.sort_by_key(|a| a.checks_count());
+        let lived = all_heuristics(&self.env);
+
+        // Pick this generation of survivors and check for the fittest or continue.
+        if self.env.len() < config.survivors {
+            return Err(Error::Extinct);
+        }
+        let diversity = diversity::diversity(&self.env);
+        let costs = match config.sharing_radius {
+            Some(radius) => diversity::shared_costs(&self.env, radius),
+            None => lived.iter().map(|&c| c as f64).collect(),
+        };
+        let mut survivors_vec = config
+            .selection
+            .select(&costs, config.survivors, rng)
+            .into_iter()
+            .map(|i| self.env[i].clone())
+            .collect::<Vec<Board>>();
+        survivors_vec.sort_by_key(|a| a.checks_count());
+        let survivors = all_heuristics(&survivors_vec);
+        let mutation_chance = if config.adaptive_mutation {
+            self.mutation_rate.update(lived[0])
+        } else {
+            config.mutation_chance
+        };
+        if lived[0] == 0 {
+            return Ok(Generation {
+                born,
+                lived,
+                survivors,
+                diversity,
+                mutation_chance,
+                repaired: 0,
+                discarded: 0,
+                best: self.env[0].clone(),
+                fittest: Some(self.env[0].clone()),
+            });
+        }
+
+        let best = self.env[0].clone();
+        let (env, repaired, discarded) = breed(config, mutation_chance, &survivors_vec, rng);
+        self.lived = std::mem::replace(&mut self.env, env);
+        Ok(Generation {
+            born,
+            lived,
+            survivors,
+            diversity,
+            mutation_chance,
+            repaired,
+            discarded,
+            best,
+            fittest: None,
+        })
+    }
+}
+
+/// What happened in an epoch of the island model (the generations between two migrations).
+#[derive(Debug, Clone)]
+pub struct Epoch {
+    /// The number of generations evolved so far.
+    pub generation: usize,
+    /// The heuristics of the boards of each island before the migration.
+    pub heuristics: Vec<Vec<usize>>,
+    /// The islands which went extinct and were started again with random boards.
+    pub reseeded: Vec<usize>,
+}
+
+/// Several populations (islands) evolving in parallel and exchanging their fittest boards.
+///
+/// Every `interval` generations, the `migrants` fittest boards of each island move to the next
+/// island (ring) or to every other island (fully connected) and replace their least fit.
+#[derive(Debug, Clone)]
+pub struct Islands {
+    envs: Vec<Population>,
+    /// The islands draw from their own generators so the threads do not change the outcome.
+    rngs: Vec<StdRng>,
+    topology: Topology,
+    migrants: usize,
+    interval: usize,
+}
+
+impl Islands {
+    /// Create an island for each config, seeding their generators from `rng`.
+    pub fn new<R: Rng>(
+        configs: Vec<Config>,
+        topology: Topology,
+        migrants: usize,
+        interval: usize,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let envs = configs
+            .into_iter()
+            .map(|config| Population::new(config, rng))
+            .collect::<Result<Vec<Population>, Error>>()?;
+        let rngs = envs
+            .iter()
+            .map(|_| StdRng::seed_from_u64(rng.gen()))
+            .collect();
+        Ok(Self {
+            envs,
+            rngs,
+            topology,
+            migrants,
+            interval: interval.max(1),
+        })
+    }
+
+    /// A getter for the populations.
+    pub fn populations(&self) -> &Vec<Population> {
+        &self.envs
+    }
+
+    /// Evolve the islands for up to `generations` generations, calling `observer` after each
+    /// epoch.
+    ///
+    /// An island which goes extinct is started again with random boards. Returns the island and
+    /// the board without any checks once one is found.
+    pub fn run<F: FnMut(&Epoch)>(
+        &mut self,
+        generations: usize,
+        mut observer: F,
+    ) -> Result<Option<(usize, Board)>, Error> {
+        let mut generation = 0;
+        while generation < generations {
+            let epoch = self.interval.min(generations - generation);
+            // Each island evolves on its own thread until the next migration.
+            let results = thread::scope(|s| {
+                let workers = self
+                    .envs
+                    .iter_mut()
+                    .zip(self.rngs.iter_mut())
+                    .map(|(env, rng)| s.spawn(move || evolve_island(env, epoch, rng)))
+                    .collect::<Vec<_>>();
+                workers
+                    .into_iter()
+                    .map(|w| w.join().expect("An island panicked"))
+                    .collect::<Vec<Result<IslandEpoch, Error>>>()
+            });
+            generation += epoch;
+
+            let mut reseeded = vec![];
+            for (i, result) in results.into_iter().enumerate() {
+                match result? {
+                    IslandEpoch::Solved(fittest) => return Ok(Some((i, fittest))),
+                    IslandEpoch::Evolved => {}
+                    IslandEpoch::Reseeded => reseeded.push(i),
+                }
+            }
+            observer(&Epoch {
+                generation,
+                heuristics: self.envs.iter().map(|env| env.heuristics()).collect(),
+                reseeded,
+            });
+            self.migrate();
+        }
+        Ok(None)
+    }
+
+    /// Move the fittest boards of each island to its neighbours.
+    fn migrate(&mut self) {
+        let islands = self.envs.len();
+        let emigrants = self
+            .envs
+            .iter()
+            .map(|env| env.fittest(self.migrants))
+            .collect::<Vec<Vec<Board>>>();
+        for (i, env) in self.envs.iter_mut().enumerate() {
+            let immigrants = match self.topology {
+                Topology::Ring => emigrants[(i + islands - 1) % islands].clone(),
+                Topology::Full => (0..islands)
+                    .filter(|&j| j != i)
+                    .flat_map(|j| emigrants[j].clone())
+                    .collect(),
+            };
+            env.immigrate(immigrants);
+        }
+    }
+}
+
+/// How the epoch of a single island ended.
+enum IslandEpoch {
+    /// A board without any checks was found.
+    Solved(Board),
+    /// Every generation of the epoch evolved.
+    Evolved,
+    /// The island went extinct and was started again with random boards.
+    Reseeded,
+}
+
+/// Evolve an island for an epoch.
+fn evolve_island(
+    env: &mut Population,
+    epoch: usize,
+    rng: &mut StdRng,
+) -> Result<IslandEpoch, Error> {
+    for _ in 0..epoch {
+        match env.evolve(rng) {
+            Ok(Generation {
+                fittest: Some(fittest),
+                ..
+            }) => return Ok(IslandEpoch::Solved(fittest)),
+            Ok(_) => {}
+            Err(Error::Extinct) => {
+                *env = Population::new(env.config().clone(), rng)?;
+                return Ok(IslandEpoch::Reseeded);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(IslandEpoch::Evolved)
+}
+
+fn all_heuristics(env: &[Board]) -> Vec<usize> {
+    env.iter().map(|i| i.checks_count()).collect()
+}
+
+/// Make the children of the next generation from the survivors.
+///
+/// Children with two queens on the same square are repaired, and a whole population of them is
+/// made. Without `config.repair`, only `population / parents` children are made and the colliding
+/// ones are left to die, so the population shrinks (the original behavior).
+///
+/// Returns the children and the number of the repaired and discarded ones.
+fn breed<R: Rng>(
+    config: &Config,
+    mutation_chance: usize,
+    survivors_vec: &[Board],
+    rng: &mut R,
+) -> (Vec<Board>, usize, usize) {
+    let Config {
+        n,
+        population,
+        parents,
+        crossover,
+        mutation,
+        repair,
+        ..
+    } = *config;
+
+    let mut env = vec![];
+    let mut repaired = 0;
+    let mut discarded = 0;
+    // Make children from the survivors
+    let children = if repair {
+        population
+    } else {
+        population / parents
+    };
+    'child_production: for _child in 0..children {
+        // Choose some parents to make a child from them
+        let mut parents_vec = Vec::<Board>::with_capacity(parents);
+        for _ in 0..parents {
+            // NOTE Since this is not important, we leave the chance for a board to have
+            // children from itself.
+            let randomly_picked_parent =
+                survivors_vec[rng.gen_range(0..survivors_vec.len())].clone();
+            parents_vec.push(randomly_picked_parent);
+        }
+
+        // Create the child from their stats and distribute the information/genes.
+        let mut child_genes = crossover.apply(&parents_vec, rng);
+
+        // Mutate the child genes.
+        for i in 0..child_genes.len() {
+            if rng.gen_range(0..100) < mutation_chance {
+                mutation.apply(&mut child_genes, i, n, rng);
+            }
+        }
+        // Check for cancer (two pieces in the same coord)
+        // Try to place the genes on a new board and if successful add it to the env, else
+        // repair the gene or leave the child to die (cancerous).
+        let mut child = Board::new(n);
+        let mut cancerous = false;
+        for i in child_genes {
+            if child.place(&i).is_ok() {
+                continue;
+            }
+            cancerous = true;
+            match relocation(&child, &i) {
+                Some(free) if repair => child.place(&free).unwrap(),
+                _ => {
+                    discarded += 1;
+                    continue 'child_production;
+                }
+            }
+        }
+        if cancerous {
+            repaired += 1;
+        }
+
+        // Healthy child release to the environment.
+        env.push(child);
+    }
+    (env, repaired, discarded)
+}
+
+/// The free square of the board with the fewest threats, the nearest one to `point` if several
+/// have as few.
+fn relocation(board: &Board, point: &Point) -> Option<Point> {
+    let n = board.n();
+    let distance = |p: &Point| p.row.abs_diff(point.row).max(p.col.abs_diff(point.col));
+    (0..n)
+        .flat_map(|row| (0..n).map(move |col| Point::new(row, col)))
+        .filter(|p| board.index_of(p).is_none())
+        .min_by_key(|p| (board.threats_to(p, None), distance(p)))
+}
+
+/// The number of genes each parent passes in the split crossovers: `n / parents` each, the last
+/// parent giving the leftover genes too.
+fn portions(n: usize, parents: usize) -> Vec<usize> {
+    let mut v = vec![n / parents; parents];
+    v[parents - 1] += n % parents;
+    v
+}
+
+/// In this example implementation, the gene split rate is uniform meaning all parents pass equal
+/// amount of genes to their children.
+fn split(parents: &[Board]) -> Vec<Point> {
+    let n = parents[0].queens().len();
+    let mut child_genes = Vec::<Point>::with_capacity(n);
+    for (parent, portion) in parents.iter().zip(portions(n, parents.len())) {
+        // Pick the first n/PARENTS genes from the first parent then the next till one is left
+        let start = child_genes.len();
+        child_genes.extend_from_slice(&parent.queens()[start..(start + portion)]);
+    }
+    child_genes
+}
+
+fn k_point<R: Rng>(parents: &[Board], k: usize, rng: &mut R) -> Vec<Point> {
+    let n = parents[0].queens().len();
+    let mut cuts = (1..n).choose_multiple(rng, k.min(n.saturating_sub(1)));
+    cuts.sort_unstable();
+    cuts.push(n);
+    let mut child_genes = Vec::<Point>::with_capacity(n);
+    for (piece, end) in cuts.into_iter().enumerate() {
+        let parent = &parents[piece % parents.len()];
+        let start = child_genes.len();
+        child_genes.extend_from_slice(&parent.queens()[start..end]);
+    }
+    child_genes
+}
+
+fn conflict_aware(parents: &[Board]) -> Vec<Point> {
+    let n = parents[0].queens().len();
+    let mut child_genes = Vec::<Point>::with_capacity(n);
+    for (parent, portion) in parents.iter().zip(portions(n, parents.len())) {
+        let mut queens = parent
+            .queens()
+            .iter()
+            .zip(parent.check_data())
+            .map(|(q, threats)| (threats.len(), q))
+            .collect::<Vec<(usize, &Point)>>();
+        queens.sort();
+        // The queens on free squares first, then the rest for the repair to handle.
+        let (free, taken): (Vec<&Point>, Vec<&Point>) = queens
+            .into_iter()
+            .map(|(_, q)| q)
+            .partition(|q| !child_genes.contains(q));
+        child_genes.extend(free.into_iter().chain(taken).take(portion).cloned());
+    }
+    child_genes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::Truncation;
+
+    fn config(n: usize, population: usize, survivors: usize) -> Config {
+        Config {
+            n,
+            population,
+            parents: 2,
+            survivors,
+            selection: Arc::new(Truncation),
+            sharing_radius: None,
+            crossover: Crossover::Split,
+            mutation_chance: 5,
+            mutation: Mutation::Reset,
+            repair: true,
+            adaptive_mutation: false,
+            moves_in_generation: 0,
+        }
+    }
+
+    #[test]
+    fn cataclysm_keeps_the_best_board() {
+        let mut rng = StdRng::seed_from_u64(2);
+        // Every gene mutates, so the children are hardly ever as fit as their parents.
+        let config = Config {
+            mutation_chance: 100,
+            ..config(12, 20, 4)
+        };
+        let mut env = Population::new(config, &mut rng).unwrap();
+        for _ in 0..5 {
+            let mut best = usize::MAX;
+            for _ in 0..3 {
+                best = env.evolve(&mut rng).unwrap().best.checks_count();
+            }
+            env.cataclysm(1, &mut rng).unwrap();
+            // The elite has no moves to make, so it is still the best board of the next generation.
+            let generation = env.evolve(&mut rng).unwrap();
+            assert!(generation.best.checks_count() <= best);
+        }
+    }
+
+    #[test]
+    fn extinct_islands_are_reseeded() {
+        let mut rng = StdRng::seed_from_u64(1);
+        // Without repairs only half a population is born and most of it is discarded, so the first
+        // island dies out.
+        let dying = Config {
+            repair: false,
+            mutation_chance: 100,
+            ..config(20, 10, 5)
+        };
+        let configs = vec![dying, config(20, 10, 2)];
+        let mut islands = Islands::new(configs, Topology::Ring, 1, 2, &mut rng).unwrap();
+        let mut reseeded = vec![];
+        let found = islands.run(6, |epoch| {
+            // A reseeded island starts again with a whole population.
+            for &i in &epoch.reseeded {
+                assert_eq!(epoch.heuristics[i].len(), 10);
+            }
+            reseeded.extend(epoch.reseeded.iter().copied());
+        });
+        assert_eq!(found, Ok(None));
+        assert!(reseeded.contains(&0));
+        assert!(!reseeded.contains(&1));
+    }
+}
@@ -0,0 +1,82 @@
+//! Random-restart steepest-ascent hill climbing over the `Board`.
+//!
+//! Every step evaluates all the legal single-queen moves and takes the one leaving the fewest
+//! checks. When no move improves the board (after some sideways moves which keep it the same),
+//! the climb starts over from a new random board.
+
+use rand::prelude::*;
+
+use crate::{Board, Error};
+
+/// The parameters of a hill climb.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The number of consecutive moves which do not change the checks allowed before restarting.
+    pub sideways: usize,
+    /// The maximum number of restarts before giving up.
+    pub max_restarts: usize,
+}
+
+/// The outcome of a hill climb.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The board with the fewest checks seen.
+    pub best: Board,
+    /// The number of restarts made.
+    pub restarts: usize,
+    /// The number of moves made (over all the restarts).
+    pub moves: usize,
+    /// The number of the moves which did not change the checks.
+    pub sideways_moves: usize,
+}
+
+/// Climb from random boards of `n` queens until no checks are left or the restarts run out.
+pub fn climb<R: Rng>(n: usize, options: &Options, rng: &mut R) -> Result<Report, Error> {
+    let mut report = Report {
+        best: Board::new(n).init_n_queens(rng)?,
+        restarts: 0,
+        moves: 0,
+        sideways_moves: 0,
+    };
+    let mut board = report.best.clone();
+    let mut sideways_left = options.sideways;
+
+    loop {
+        let cost = board.checks_count();
+        if cost < report.best.checks_count() {
+            report.best = board.clone();
+        }
+        if cost == 0 {
+            break;
+        }
+
+        let mut stuck = true;
+        let moves = board.moves();
+        if let Some(min) = moves.iter().map(|m| m.2).min() {
+            if min < cost || (min == cost && sideways_left > 0) {
+                let best_moves = moves.iter().filter(|m| m.2 == min).collect::<Vec<_>>();
+                let (from, to, _) = best_moves.choose(rng).unwrap();
+                board.mov(from, to)?;
+                report.moves += 1;
+                if min == cost {
+                    sideways_left -= 1;
+                    report.sideways_moves += 1;
+                } else {
+                    sideways_left = options.sideways;
+                }
+                stuck = false;
+            }
+        }
+
+        if stuck {
+            if report.restarts == options.max_restarts {
+                break;
+            }
+            report.restarts += 1;
+            board = Board::new(n).init_n_queens(rng)?;
+            sideways_left = options.sideways;
+        }
+    }
+
+    Ok(report)
+}
@@ -1,11 +1,32 @@
 //! The library holding implementation free structs of N-Queen.
 
-// TODO replace prints with logs
-
 use rand::prelude::*;
 use std::fmt::Display;
-
-const ALREADY_FILLED_POINT_ERROR: &str = "The point is already taken.";
+use std::str::FromStr;
+
+mod error;
+
+pub use error::Error;
+
+pub mod anneal;
+pub mod backtrack;
+pub mod beam;
+pub mod construct;
+pub mod count;
+pub mod csp;
+pub mod diversity;
+pub mod dlx;
+pub mod export;
+pub mod genetic;
+pub mod hill_climb;
+pub mod min_conflicts;
+pub mod mutation;
+pub mod permutation;
+pub mod sat;
+pub mod selection;
+pub mod solver;
+pub mod symmetry;
+pub mod tabu;
 
 /// A point on a chess board.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
@@ -20,8 +41,7 @@ impl Point {
     }
 
     /// Create a random point on an `n^2` square.
-    pub fn random(n: usize) -> Self {
-        let mut rng = thread_rng();
+    pub fn random<R: Rng>(n: usize, rng: &mut R) -> Self {
         Self {
             row: rng.gen_range(0..n),
             col: rng.gen_range(0..n),
@@ -51,6 +71,26 @@ impl Display for Point {
     }
 }
 
+/// Parses the same format as `Display` (`(ROWxCOL)` counting from 1), the parentheses are optional.
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let format_error = || Error::BadPoint(s.to_string());
+        let s = s.trim();
+        let s = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(s);
+        let (row, col) = s.split_once('x').ok_or_else(format_error)?;
+        let parse = |i: &str| match i.trim().parse::<usize>() {
+            Ok(i) if i > 0 => Ok(i - 1),
+            _ => Err(format_error()),
+        };
+        Ok(Self::new(parse(row)?, parse(col)?))
+    }
+}
+
 /// Represents an `n*n` chess board.
 #[derive(Debug, Clone)]
 pub struct Board {
@@ -74,29 +114,58 @@ impl Board {
         }
     }
 
+    /// Create a board with the given queens already on it.
+    ///
+    /// Unlike calling `place` repeatedly, the check data is only calculated once.
+    pub fn with_queens(n: usize, queens: Vec<Point>) -> Result<Self, Error> {
+        if let Some(p) = queens.iter().find(|p| p.row >= n || p.col >= n) {
+            return Err(Error::OutOfBounds(p.clone()));
+        }
+        let mut sorted = queens.clone();
+        sorted.sort();
+        if let Some(w) = sorted.windows(2).find(|w| w[0] == w[1]) {
+            return Err(Error::SquareOccupied(w[0].clone()));
+        }
+        let mut board = Self::new(n);
+        board.queens = queens;
+        board.update_check_data();
+        Ok(board)
+    }
+
+    /// Create an `n*n` board (`n` being the length of `cols`) with the queen of each row on the
+    /// given column.
+    ///
+    /// Fails if a column is out of the board or used twice.
+    pub fn from_permutation(cols: &[usize]) -> Result<Self, Error> {
+        let queens = cols
+            .iter()
+            .enumerate()
+            .map(|(row, col)| Point::new(row, *col))
+            .collect();
+        Self::with_queens(cols.len(), queens)
+    }
+
     /// Place some number of `queens` randomly on the board.
     // TODO Optimize the large loop.
-    pub fn init_queens(mut self, queens: usize) -> Result<Self, &'static str> {
+    pub fn init_queens<R: Rng>(mut self, queens: usize, rng: &mut R) -> Result<Self, Error> {
         let mut placed_queens = 0;
         const MAX_TRIES: usize = 100000;
         for _ in 0..MAX_TRIES {
             if placed_queens == queens {
-                println!("Queens: {}", self.queens_display());
-                println!("{}", self);
                 return Ok(self);
             }
-            if self.place(&Point::random(self.n)).is_ok() {
+            if self.place(&Point::random(self.n, rng)).is_ok() {
                 placed_queens += 1;
             }
         }
 
-        Err("Could not place more queens on the board (is it filled?)")
+        Err(Error::BoardFull)
     }
 
     /// Place N queens on the board randomly.
-    pub fn init_n_queens(self) -> Result<Self, &'static str> {
+    pub fn init_n_queens<R: Rng>(self, rng: &mut R) -> Result<Self, Error> {
         let n = self.n;
-        self.init_queens(n)
+        self.init_queens(n, rng)
     }
 
     /// A getter for the queens data.
@@ -114,31 +183,48 @@ impl Board {
         self.max_checks
     }
 
+    /// A getter for the size of the board.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
     /// Place a Queen on a given point.
-    pub fn place(&mut self, point: &Point) -> Result<(), &'static str> {
-        if self.queens.contains(point) {
-            Err(ALREADY_FILLED_POINT_ERROR)
-        } else {
-            self.queens.push(point.clone());
-            self.update_check_data();
-            Ok(())
-        }
+    pub fn place(&mut self, point: &Point) -> Result<(), Error> {
+        self.check_free(point)?;
+        self.queens.push(point.clone());
+        self.update_check_data();
+        Ok(())
     }
 
     /// Removes a Queen from the game.
-    pub fn capture(&mut self, point: &Point) {
-        self.queens.remove(self.index_of(point).unwrap());
+    pub fn capture(&mut self, point: &Point) -> Result<(), Error> {
+        let i = self
+            .index_of(point)
+            .ok_or_else(|| Error::NoQueenAt(point.clone()))?;
+        self.queens.remove(i);
         self.update_check_data();
+        Ok(())
     }
 
     /// Move a Queen to another position.
-    pub fn mov(&mut self, from: &Point, to: &Point) -> Result<(), &'static str> {
-        if self.queens.contains(to) {
-            Err(ALREADY_FILLED_POINT_ERROR)
+    pub fn mov(&mut self, from: &Point, to: &Point) -> Result<(), Error> {
+        let i = self
+            .index_of(from)
+            .ok_or_else(|| Error::NoQueenAt(from.clone()))?;
+        self.check_free(to)?;
+        self.queens.remove(i);
+        self.queens.push(to.clone());
+        self.update_check_data();
+        Ok(())
+    }
+
+    /// Fails if the point is out of the board or already has a queen.
+    fn check_free(&self, point: &Point) -> Result<(), Error> {
+        if point.row >= self.n || point.col >= self.n {
+            Err(Error::OutOfBounds(point.clone()))
+        } else if self.queens.contains(point) {
+            Err(Error::SquareOccupied(point.clone()))
         } else {
-            self.queens.push(to.clone());
-            self.queens.remove(self.index_of(from).unwrap());
-            self.update_check_data();
             Ok(())
         }
     }
@@ -148,9 +234,57 @@ impl Board {
         if queen1 == queen2 {
             return false;
         }
-        let (x1, y1): (i32, i32) = queen1.try_into().unwrap();
-        let (x2, y2): (i32, i32) = queen2.try_into().unwrap();
-        x1 == x2 || y1 == y2 || (x1 - x2).abs() == (y1 - y2).abs() /* diagonal */
+        queen1.row == queen2.row
+            || queen1.col == queen2.col
+            || queen1.row.abs_diff(queen2.row) == queen1.col.abs_diff(queen2.col)
+        /* diagonal */
+    }
+
+    /// The number of queens (other than the one on `ignored`) which would check a queen on `point`.
+    ///
+    /// Moving a queen from `from` to `to` changes `checks_count` by
+    /// `threats_to(to, Some(from)) - threats_to(from, None)` without having to make the move.
+    pub fn threats_to(&self, point: &Point, ignored: Option<&Point>) -> usize {
+        self.queens
+            .iter()
+            .filter(|q| Some(*q) != ignored && Self::checking(q, point))
+            .count()
+    }
+
+    /// Every legal single-queen move as `(from, to, checks_count after the move)`.
+    ///
+    /// The moves are evaluated with `threats_to` so the board is not changed.
+    pub fn moves(&self) -> Vec<(Point, Point, usize)> {
+        self.queens
+            .iter()
+            .flat_map(|from| {
+                self.moves_of(from)
+                    .expect("`from` is a queen of the board")
+                    .into_iter()
+                    .map(move |(to, cost)| (from.clone(), to, cost))
+            })
+            .collect()
+    }
+
+    /// Every legal move of the queen on `from` as `(to, checks_count after the move)`.
+    ///
+    /// Fails if there is no queen on `from`.
+    pub fn moves_of(&self, from: &Point) -> Result<Vec<(Point, usize)>, Error> {
+        if self.index_of(from).is_none() {
+            return Err(Error::NoQueenAt(from.clone()));
+        }
+        let leaving = self.checks_count() - self.threats_to(from, None);
+        let mut v = vec![];
+        for row in 0..self.n {
+            for col in 0..self.n {
+                let to = Point::new(row, col);
+                if self.index_of(&to).is_none() {
+                    let cost = leaving + self.threats_to(&to, Some(from));
+                    v.push((to, cost));
+                }
+            }
+        }
+        Ok(v)
     }
 
     /// Update the list of all the Queens that are checking each other.
@@ -178,13 +312,14 @@ impl Board {
         self.max_checks = (self.queens.len() * self.queens.len().saturating_sub(1)) / 2;
     }
 
-    pub fn random_point(&self) -> Point {
-        Point::random(self.n)
+    pub fn random_point<R: Rng>(&self, rng: &mut R) -> Point {
+        Point::random(self.n, rng)
     }
 
     /// Return the index of the queen which is under the most threat.
     ///
     /// Returns nothing if there is no queen on the board.
+    #[allow(clippy::manual_map)]
     pub fn most_checked(&self) -> Option<usize> {
         // take out the original index and the data to sort them and be able to track the movements
         let mut check_data = self
@@ -194,9 +329,9 @@ impl Board {
             .map(|(i, v)| (i, v.clone()))
             .collect::<Vec<(usize, Vec<Point>)>>();
         // sort by the number of threats
-        check_data.sort_by(|a, b| a.1.len().partial_cmp(&b.1.len()).unwrap());
+        check_data.sort_by_key(|v| v.1.len());
         match check_data.last() {
-            Some(v) => Some(v.1.len()),
+            Some(v) => Some(v.0),
             None => None,
         }
     }
@@ -205,22 +340,46 @@ impl Board {
     ///
     /// Returns the source and destination.
     ///
-    /// # Caveats
-    /// - Pancis if called on a board with no queens.
-    /// - Loops forever if the board is filled as it places the queen randomly.
-    pub fn move_most_checked(&mut self) -> (Point, Point) {
-        let most_checked_index = self
-            .most_checked()
-            .expect("Place a queen on the board before trying to move the most checked");
+    /// Fails if there is no queen or no free square on the board.
+    pub fn move_most_checked<R: Rng>(&mut self, rng: &mut R) -> Result<(Point, Point), Error> {
+        let most_checked_index = self.most_checked().ok_or(Error::NoQueens)?;
+        if self.queens.len() >= self.n * self.n {
+            return Err(Error::BoardFull);
+        }
         let src = self.queens[most_checked_index].clone();
-        let mut dest = self.random_point();
+        let mut dest = self.random_point(rng);
         loop {
             if self.mov(&src, &dest).is_ok() {
                 break;
             }
-            dest = self.random_point();
+            dest = self.random_point(rng);
+        }
+        Ok((src, dest))
+    }
+
+    /// Move a piece the most checked only if the heuristic shows a lower value.
+    ///
+    /// Breaks after fixed number of attempts.
+    ///
+    /// The checks/threats count is the heuristic function in this implementation.
+    ///
+    /// Returns the heuristic before and after the move and the source and destination.
+    pub fn lower_heuristic<R: Rng>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<(usize, usize, Point, Point), Error> {
+        const MAX_ATTEMPTS: usize = 1000000;
+        for _ in 0..MAX_ATTEMPTS {
+            let pre_h = self.checks_count();
+            let (from, to) = self.move_most_checked(rng)?;
+            let post_h = self.checks_count();
+            if pre_h < post_h {
+                self.mov(&to, &from)?;
+            } else {
+                return Ok((pre_h, post_h, from, to));
+            }
         }
-        (src, dest)
+        Err(Error::LocalMinimum)
     }
 
     /// Search for a queen at a certain position.
@@ -252,6 +411,7 @@ impl Board {
 }
 
 impl Display for Board {
+    #[allow(clippy::write_with_newline)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let n = self.n;
 
@@ -5,16 +5,36 @@
 #[macro_use]
 extern crate clap;
 
-use clap::{arg, command, Parser};
-use rand::prelude::*;
+use clap::Parser;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use nqueen::{Board, Point};
+use nqueen::anneal::{self, Schedule};
+use nqueen::csp::Inference;
+use nqueen::export::Format;
+use nqueen::genetic::{self, Islands, StopCriteria, Topology};
+use nqueen::mutation::Mutation;
+use nqueen::permutation::{self, Crossover};
+use nqueen::selection::{self, Selection};
+use nqueen::solver::{self, Event, GeneticConfig, RandomConfig, Solver};
+use nqueen::{
+    backtrack, beam, construct, count, csp, dlx, export, hill_climb, min_conflicts::MinConflicts,
+    sat, symmetry, tabu, Board, Error, Point,
+};
 
 #[derive(Parser, Debug)]
 #[command(about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     mode: ModeCommands,
+    /// Seed the random generator to replay a run (a random seed is picked otherwise)
+    #[arg(long, global = true, value_name = "SEED")]
+    seed: Option<u64>,
 }
 
 /// The possible modes/algorithms of the program.
@@ -29,6 +49,269 @@ enum ModeCommands {
         #[arg(value_parser = clap::value_parser!(u16).range(4..))]
         n: u16,
     },
+    /// Use an exact backtracking search
+    Backtrack {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+        n: u16,
+    },
+    /// Count all the solutions with a bitmask search
+    Count {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..=64))]
+        n: u16,
+        /// List one representative of each solution up to rotation and reflection
+        #[arg(short, long)]
+        fundamental: bool,
+        /// The number of worker threads counting in parallel
+        #[arg(short, long, value_name = "THREADS", default_value_t = 1)]
+        threads: usize,
+        /// The number of rows placed to split the search into tasks for the threads
+        #[arg(short, long, value_name = "ROWS", default_value_t = 3)]
+        depth: usize,
+    },
+    /// Use a min-conflicts local search (fit for millions of queens)
+    MinConflicts {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u32).range(4..))]
+        n: u32,
+        /// The maximum number of moves before giving up
+        #[arg(short, long, value_name = "STEPS", default_value_t = 1000000)]
+        max_steps: usize,
+    },
+    /// Use simulated annealing
+    Anneal {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The cooling schedule (exponential, linear or logarithmic)
+        #[arg(short, long, value_name = "SCHEDULE", default_value_t = Schedule::Exponential)]
+        schedule: Schedule,
+        /// The temperature at the start and after each reheat
+        #[arg(short, long, value_name = "TEMPERATURE", default_value_t = 2.0)]
+        temperature: f64,
+        /// The parameter of the schedule (ratio for exponential, decrement for linear, scale for
+        /// logarithmic) [default: 0.999, 0.0004 or 1 respectively]
+        #[arg(short, long, value_name = "RATE")]
+        cooling_rate: Option<f64>,
+        /// Restart the schedule after this many steps without a new best heuristic
+        #[arg(short, long, value_name = "STEPS")]
+        reheat_after: Option<usize>,
+        /// The maximum number of steps
+        #[arg(short, long, value_name = "STEPS", default_value_t = 100000)]
+        max_steps: usize,
+    },
+    /// Use tabu search
+    Tabu {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The number of iterations a queen may not return to a square it left
+        #[arg(short, long, value_name = "ITERATIONS", default_value_t = 10)]
+        tenure: usize,
+        /// The maximum number of iterations
+        #[arg(short, long, value_name = "ITERATIONS", default_value_t = 10000)]
+        max_iterations: usize,
+    },
+    /// Build a solution directly from the known patterns (fit for millions of queens)
+    Construct {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+        n: u32,
+    },
+    /// Use steepest-ascent hill climbing with random restarts
+    HillClimb {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The number of consecutive moves which do not lower the heuristic before restarting
+        #[arg(short, long, value_name = "MOVES", default_value_t = 100)]
+        sideways: usize,
+        /// The maximum number of restarts
+        #[arg(short, long, value_name = "RESTARTS", default_value_t = 1000)]
+        max_restarts: usize,
+    },
+    /// Use local beam search over several boards
+    Beam {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The number of boards kept at each step
+        #[arg(short, long, value_name = "BOARDS", default_value_t = 10)]
+        k: usize,
+        /// Sample the successors by fitness instead of keeping the best ones
+        #[arg(short, long)]
+        stochastic: bool,
+        /// The maximum number of steps
+        #[arg(short, long, value_name = "STEPS", default_value_t = 1000)]
+        max_steps: usize,
+    },
+    /// Use Dancing Links (Algorithm X) over the exact cover form of the problem
+    Dlx {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+        n: u16,
+        /// Count all the solutions instead of listing them
+        #[arg(short, long)]
+        count: bool,
+        /// The maximum number of solutions listed
+        #[arg(short, long, value_name = "SOLUTIONS", default_value_t = 1)]
+        limit: usize,
+        /// A square which must have a queen (like 3x5, counting from 1)
+        #[arg(short, long, value_name = "POINT")]
+        place: Vec<Point>,
+        /// A square which must not have a queen (like 3x5, counting from 1)
+        #[arg(short, long, value_name = "POINT")]
+        block: Vec<Point>,
+    },
+    /// Use a constraint satisfaction search with one variable per row
+    Csp {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+        n: u16,
+        /// How the domains are pruned (none, forward-checking or ac3)
+        #[arg(short, long, value_name = "INFERENCE", default_value_t = Inference::ForwardChecking)]
+        inference: Inference,
+        /// Take the rows in order instead of by minimum remaining values
+        #[arg(long)]
+        no_mrv: bool,
+        /// Take the columns in order instead of by least constraining value
+        #[arg(long)]
+        no_lcv: bool,
+    },
+    /// Use the SAT encoding with the built-in CDCL solver (or export it as DIMACS CNF)
+    Sat {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+        n: u16,
+        /// Write the encoding to this file as DIMACS CNF
+        #[arg(short, long, value_name = "FILE")]
+        export: Option<PathBuf>,
+        /// Decode the model an external solver wrote to this file instead of solving
+        #[arg(short, long, value_name = "FILE")]
+        model: Option<PathBuf>,
+    },
+    /// Write the model for external constraint tools (or read their answer back)
+    Export {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(1..))]
+        n: u16,
+        /// The format of the model (mzn, smt2 or lp)
+        #[arg(short, long, value_name = "FORMAT", default_value_t = Format::MiniZinc)]
+        format: Format,
+        /// Write the model to this file instead of the standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// A square which must have a queen (like 3x5, counting from 1)
+        #[arg(short, long, value_name = "POINT")]
+        place: Vec<Point>,
+        /// Read the answer of the external tool from this file instead of writing the model
+        #[arg(short, long, value_name = "FILE")]
+        import: Option<PathBuf>,
+    },
+    /// Use several genetic populations (islands) evolving in parallel with migration
+    Islands {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The number of islands
+        #[arg(short, long, value_name = "ISLANDS", default_value_t = 4)]
+        islands: usize,
+        /// The initial population of each island (repeated for the rest of the islands)
+        #[arg(
+            short,
+            long,
+            value_name = "POPULATION",
+            value_delimiter = ',',
+            default_value = "30"
+        )]
+        population: Vec<usize>,
+        /// The maximum number of survivors of each island (repeated for the rest of the islands)
+        #[arg(
+            short,
+            long,
+            value_name = "SURVIVORS",
+            value_delimiter = ',',
+            default_value = "6"
+        )]
+        survivors: Vec<usize>,
+        /// How the survivors are picked (truncation, tournament[:SIZE], roulette, rank or sus)
+        #[arg(
+            long,
+            value_name = "SELECTION",
+            value_parser = selection::parse,
+            default_value = "truncation"
+        )]
+        selection: Arc<dyn Selection>,
+        /// Share the fitness of the boards closer than this many queens (fitness sharing)
+        #[arg(long, value_name = "DISTANCE")]
+        sharing_radius: Option<usize>,
+        /// The mutation percentage of each island (repeated for the rest of the islands)
+        #[arg(
+            short,
+            long,
+            value_name = "MUTATION_CHANCE",
+            value_delimiter = ',',
+            default_value = "5"
+        )]
+        mutation_chance: Vec<usize>,
+        /// How a gene is mutated (reset, swap, inversion, scramble or min-conflicts)
+        #[arg(long, value_name = "MUTATION", default_value_t = Mutation::Reset)]
+        mutation: Mutation,
+        /// Discard the children with colliding queens instead of repairing them (the original mode)
+        #[arg(long)]
+        no_repair: bool,
+        /// Raise the mutation chance while the fittest board does not get better
+        #[arg(long)]
+        adaptive_mutation: bool,
+        /// The number of parents that combine into a single child
+        #[arg(short = 'r', long, value_name = "PARENTS", default_value_t = 2)]
+        parents: usize,
+        /// How the parents are combined (split, k-point[:K], uniform or conflict-aware)
+        #[arg(long, value_name = "CROSSOVER", default_value_t = genetic::Crossover::Split)]
+        crossover: genetic::Crossover,
+        /// The number of moves in each generation
+        #[arg(
+            short = 'd',
+            long,
+            value_name = "MOVES_IN_GENERATION",
+            default_value_t = 3
+        )]
+        moves_in_generation: usize,
+        /// The number of generations between migrations
+        #[arg(short = 'k', long, value_name = "GENERATIONS", default_value_t = 10)]
+        migration_interval: usize,
+        /// The number of the fittest boards leaving each island on a migration
+        #[arg(short = 'e', long, value_name = "MIGRANTS", default_value_t = 2)]
+        migrants: usize,
+        /// Where the migrants go (ring or full)
+        #[arg(short, long, value_name = "TOPOLOGY", default_value_t = Topology::Ring)]
+        topology: Topology,
+        /// The maximum number of generations
+        #[arg(short, long, value_name = "GENERATIONS", default_value_t = 1000)]
+        generations: usize,
+    },
+    /// Use a genetic algorithm over permutations (one queen per row and column)
+    PermutationGenetic {
+        /// The size of the board and number of queens
+        #[arg(value_parser = clap::value_parser!(u16).range(4..))]
+        n: u16,
+        /// The number of boards in each generation
+        #[arg(short, long, value_name = "POPULATION", default_value_t = 100)]
+        population: usize,
+        /// The number of the fittest boards which survive and become the parents
+        #[arg(short, long, value_name = "SURVIVORS", default_value_t = 20)]
+        survivors: usize,
+        /// The percentage chance of a child having two queens swap rows
+        #[arg(short, long, value_name = "MUTATION_CHANCE", default_value_t = 20)]
+        mutation_chance: usize,
+        /// How two parents are combined (pmx, ox or cx)
+        #[arg(short, long, value_name = "CROSSOVER", default_value_t = Crossover::Pmx)]
+        crossover: Crossover,
+        /// The maximum number of generations
+        #[arg(short, long, value_name = "GENERATIONS", default_value_t = 1000)]
+        generations: usize,
+    },
     /// Use genetic algorithm
     Genetic {
         /// The size of the board and number of queens
@@ -40,12 +323,35 @@ enum ModeCommands {
         /// The number of parents that combine into a single child
         #[arg(short = 'r', long, value_name = "PARENTS", default_value_t = 2)]
         parents: usize,
+        /// How the parents are combined (split, k-point[:K], uniform or conflict-aware)
+        #[arg(long, value_name = "CROSSOVER", default_value_t = genetic::Crossover::Split)]
+        crossover: genetic::Crossover,
         /// The maximum number of suvivors moved to the next generation
         #[arg(short, long, value_name = "SURVIVORS", default_value_t = 6)]
         survivors: usize,
+        /// How the survivors are picked (truncation, tournament[:SIZE], roulette, rank or sus)
+        #[arg(
+            long,
+            value_name = "SELECTION",
+            value_parser = selection::parse,
+            default_value = "truncation"
+        )]
+        selection: Arc<dyn Selection>,
+        /// Share the fitness of the boards closer than this many queens (fitness sharing)
+        #[arg(long, value_name = "DISTANCE")]
+        sharing_radius: Option<usize>,
         /// The mutation percentage for each single gene
         #[arg(short, long, value_name = "SURVIVORS", default_value_t = 5)]
         mutation_chance: usize,
+        /// How a gene is mutated (reset, swap, inversion, scramble or min-conflicts)
+        #[arg(long, value_name = "MUTATION", default_value_t = Mutation::Reset)]
+        mutation: Mutation,
+        /// Discard the children with colliding queens instead of repairing them (the original mode)
+        #[arg(long)]
+        no_repair: bool,
+        /// Raise the mutation chance while the fittest board does not get better
+        #[arg(long)]
+        adaptive_mutation: bool,
         /// The number of moves in each generation
         #[arg(
             short = 'd',
@@ -57,178 +363,374 @@ enum ModeCommands {
         /// The maximum number of generations
         #[arg(short, long, value_name = "GENERATIONS", default_value_t = 1000)]
         generations: usize,
+        /// Stop once a board has at most this many checks
+        #[arg(long, value_name = "CHECKS", default_value_t = 0)]
+        target: usize,
+        /// Stop after this many seconds
+        #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+        time_limit: Option<Duration>,
+        /// Stop after this many generations without a better board
+        #[arg(long, value_name = "GENERATIONS")]
+        stagnation: Option<usize>,
+        /// Restart after this many generations without a better board, keeping the elite
+        #[arg(long, value_name = "GENERATIONS")]
+        cataclysm: Option<usize>,
+        /// The number of the fittest boards kept by a cataclysm
+        #[arg(long, value_name = "ELITE", default_value_t = 2)]
+        elite: usize,
     },
 }
 
 impl ModeCommands {
     /// Solve the selected mode.
-    pub fn solve(&self) {
+    pub fn solve(&self, rng: &mut StdRng) -> Result<(), Error> {
         match self {
-            Self::Random { .. } => self.random_solution(),
-            Self::Genetic { .. } => self.genetic_solution(),
+            Self::Random { .. } => self.random_solution(rng),
+            Self::Backtrack { .. } => self.backtrack_solution(),
+            Self::Count { .. } => self.count_solution(),
+            Self::MinConflicts { .. } => self.min_conflicts_solution(rng),
+            Self::Anneal { .. } => self.anneal_solution(rng),
+            Self::Tabu { .. } => self.tabu_solution(rng),
+            Self::Construct { .. } => self.construct_solution(),
+            Self::HillClimb { .. } => self.hill_climb_solution(rng),
+            Self::Beam { .. } => self.beam_solution(rng),
+            Self::Dlx { .. } => self.dlx_solution(),
+            Self::Csp { .. } => self.csp_solution(),
+            Self::Sat { .. } => self.sat_solution(),
+            Self::Export { .. } => self.export_solution(),
+            Self::Genetic { .. } => self.genetic_solution(rng),
+            Self::Islands { .. } => self.islands_solution(rng),
+            Self::PermutationGenetic { .. } => self.permutation_genetic_solution(rng),
         }
     }
 
     /// Solve the problem using the genetics algorithm.
-    fn genetic_solution(&self) {
+    fn genetic_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
         // TODO update to let-else when the new Rust is out
-        let (n, population, parents, survivors, mutation_chance, generations, moves_in_generation) =
-            match *self {
-                Self::Genetic {
+        let (config, criteria, cataclysm, elite) = match *self {
+            Self::Genetic {
+                population,
+                parents,
+                crossover,
+                survivors,
+                ref selection,
+                sharing_radius,
+                mutation_chance,
+                mutation,
+                no_repair,
+                adaptive_mutation,
+                generations,
+                moves_in_generation,
+                target,
+                time_limit,
+                stagnation,
+                cataclysm,
+                elite,
+                ..
+            } => (
+                genetic::Config {
+                    n: self.n(),
                     population,
                     parents,
                     survivors,
+                    selection: selection.clone(),
+                    sharing_radius,
+                    crossover,
                     mutation_chance,
-                    generations,
+                    mutation,
+                    repair: !no_repair,
+                    adaptive_mutation,
                     moves_in_generation,
-                    ..
-                } => (
-                    self.n(),
-                    population,
-                    parents,
-                    survivors,
-                    mutation_chance,
+                },
+                StopCriteria {
                     generations,
-                    moves_in_generation,
-                ),
-                _ => unreachable!("Invalid variant called the genetic solution"),
-            };
-
-        assert!(
-            parents < n,
-            "The chess board size is smaller than the number of parents.\
-             To keep the code simple, this is not supported."
-        );
-
-        let mut rng = thread_rng();
-
-        // Holds the boards.
-        let mut env = vec![Board::new(n); population]
-            .into_iter()
-            .map(|i| i.init_n_queens().unwrap())
-            .collect::<Vec<Board>>();
-        // Create the primitive/initial boards, the natives of the env.
-        // Print all the heuristics of the boards in the env
-        let all_heuristics =
-            |env: &Vec<Board>| env.iter().map(|i| i.checks_count()).collect::<Vec<usize>>();
+                    target,
+                    time_limit,
+                    stagnation,
+                },
+                cataclysm,
+                elite,
+            ),
+            _ => unreachable!("Invalid variant called the genetic solution"),
+        };
 
         println!(
             "Environment details:\n\
-             - initial population: {population}\n\
-             - each generation's max survivors: {survivors}\n\
-             - number of parents required for a child: {parents}\n\
-             - mutation chance: {mutation_chance}%\n\
-             - moves before a generation dies out: {moves_in_generation}\n\
-             - maximum generations: {generations}\n\
-            "
+             - initial population: {}\n\
+             - each generation's max survivors: {}\n\
+             - selection of the survivors: {:?}\n\
+             - fitness sharing radius: {:?}\n\
+             - number of parents required for a child: {}\n\
+             - crossover: {}\n\
+             - mutation chance: {}%{}\n\
+             - mutation operator: {}\n\
+             - repair colliding children: {}\n\
+             - moves before a generation dies out: {}\n\
+             - maximum generations: {}\n\
+             - target checks: {}\n\
+             - time limit: {:?}\n\
+             - stop after stagnant generations: {:?}\n\
+             - cataclysm after stagnant generations: {:?} (keeping {})\n\
+            ",
+            config.population,
+            config.survivors,
+            config.selection,
+            config.sharing_radius,
+            config.parents,
+            config.crossover,
+            config.mutation_chance,
+            if config.adaptive_mutation {
+                " (adaptive)"
+            } else {
+                ""
+            },
+            config.mutation,
+            config.repair,
+            config.moves_in_generation,
+            criteria.generations,
+            criteria.target,
+            criteria.time_limit,
+            criteria.stagnation,
+            cataclysm,
+            elite,
         );
 
-        'time: for generation in 0..generations {
-            println!("Generation #{}", generation);
-            println!("This generation's heuristics: {:?}", all_heuristics(&env));
-
-            // Let them live their lives
-            for board in env.iter_mut() {
-                for _ in 0..moves_in_generation {
-                    let _ = Self::lower_heuristic(board);
+        let (moves_in_generation, survivors, adaptive_mutation) = (
+            config.moves_in_generation,
+            config.survivors,
+            config.adaptive_mutation,
+        );
+        let mut solver = solver::Genetic::new(GeneticConfig {
+            population: config,
+            criteria,
+            cataclysm,
+            elite,
+        })
+        .observe(move |event| match event {
+            Event::Generation(generation, stats) => {
+                println!("Generation #{}", generation);
+                println!("This generation's heuristics: {:?}", stats.born);
+                println!(
+                    "This generation's heuristics after {} moves: {:?}",
+                    moves_in_generation, stats.lived
+                );
+                println!(
+                    "The {} survivors of this generation are: {:?}",
+                    survivors, stats.survivors,
+                );
+                println!(
+                    "This generation's diversity: mean distance {:.2}, closest {}, farthest {}, {} unique",
+                    stats.diversity.mean_distance,
+                    stats.diversity.min_distance,
+                    stats.diversity.max_distance,
+                    stats.diversity.unique,
+                );
+                if adaptive_mutation {
+                    println!(
+                        "The mutation chance of their children: {}%",
+                        stats.mutation_chance
+                    );
                 }
+                if stats.fittest.is_some() {
+                    println!("The fittest was found!");
+                } else {
+                    println!(
+                        "Children with colliding queens: {} repaired, {} discarded",
+                        stats.repaired, stats.discarded
+                    );
+                }
+            }
+            Event::Cataclysm(elite) => {
+                println!("Cataclysm! Keeping the {} fittest boards.", elite)
             }
+        });
 
-            // Sort by fitness
-            env.sort_by(|a, b| a.checks_count().cmp(&b.checks_count()));
+        let solution = solver.solve(rng)?;
+        if !solution.is_solved() {
             println!(
-                "This generation's heuristics after {} moves: {:?}",
-                moves_in_generation,
-                all_heuristics(&env)
+                "The best board has {} checks:",
+                solution.board.checks_count()
             );
+        }
+        println!("{}", solution.board);
+        println!(
+            "Stopped after {} generations: {}.",
+            solution.stats.generations, solution.stats.reason
+        );
+        Ok(())
+    }
+
+    /// Solve the problem using several genetic populations (islands) evolving in parallel (see
+    /// `genetic::Islands`).
+    fn islands_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let (
+            islands,
+            population,
+            survivors,
+            selection,
+            sharing_radius,
+            mutation_chance,
+            mutation,
+            no_repair,
+            adaptive_mutation,
+            parents,
+            crossover,
+            moves_in_generation,
+            migration_interval,
+            migrants,
+            topology,
+            generations,
+        ) = match self {
+            Self::Islands {
+                islands,
+                population,
+                survivors,
+                selection,
+                sharing_radius,
+                mutation_chance,
+                mutation,
+                no_repair,
+                adaptive_mutation,
+                parents,
+                crossover,
+                moves_in_generation,
+                migration_interval,
+                migrants,
+                topology,
+                generations,
+                ..
+            } => (
+                *islands,
+                population,
+                survivors,
+                selection,
+                sharing_radius,
+                mutation_chance,
+                *mutation,
+                *no_repair,
+                *adaptive_mutation,
+                *parents,
+                *crossover,
+                *moves_in_generation,
+                *migration_interval,
+                *migrants,
+                *topology,
+                *generations,
+            ),
+            _ => unreachable!("Invalid variant called the islands solution"),
+        };
+
+        // The lists of the settings are repeated if there are more islands than their values.
+        let configs = (0..islands)
+            .map(|i| {
+                let config = genetic::Config {
+                    n,
+                    population: population[i % population.len()],
+                    parents,
+                    survivors: survivors[i % survivors.len()],
+                    selection: selection.clone(),
+                    sharing_radius: *sharing_radius,
+                    crossover,
+                    mutation_chance: mutation_chance[i % mutation_chance.len()],
+                    mutation,
+                    repair: !no_repair,
+                    adaptive_mutation,
+                    moves_in_generation,
+                };
+                println!(
+                    "Island #{}: population {}, survivors {}, mutation chance {}%",
+                    i, config.population, config.survivors, config.mutation_chance
+                );
+                config
+            })
+            .collect::<Vec<genetic::Config>>();
+        let mut model = Islands::new(configs, topology, migrants, migration_interval, rng)?;
+        println!(
+            "Migrating {} boards every {} generations ({} topology), selection: {:?}, \
+             crossover: {}, mutation: {}{}\n",
+            migrants,
+            migration_interval,
+            topology,
+            selection,
+            crossover,
+            mutation,
+            if adaptive_mutation { " (adaptive)" } else { "" }
+        );
 
-            // Pick this generation of survivors and check for the fittest or continue.
-            if env.len() < survivors {
-                panic!("Everybody died!");
+        let found = model.run(generations, |epoch| {
+            for i in &epoch.reseeded {
+                println!("Island #{} went extinct and was started again.", i);
             }
-            let survivors_vec = &env[0..survivors].to_vec();
-            let survivors_heuristics = all_heuristics(&survivors_vec);
-            println!(
-                "The {} survivors of this generation are: {:?}",
-                survivors, survivors_heuristics,
-            );
-            if survivors_heuristics[0] == 0 {
-                println!("The fittest was found!");
-                println!("{}", survivors_vec[0]);
-                break 'time;
+            for (i, heuristics) in epoch.heuristics.iter().enumerate() {
+                println!(
+                    "Generation #{} island #{} heuristics: {:?}",
+                    epoch.generation, i, heuristics
+                );
             }
+        })?;
+        match found {
+            Some((i, fittest)) => {
+                println!("The fittest was found on island #{}!", i);
+                println!("{}", fittest);
+            }
+            None => println!("No solution was found in {} generations.", generations),
+        }
+        Ok(())
+    }
 
-            env = vec![];
-            // Make children from the survivors
-            'child_production: for _parents in 0..(population / parents) {
-                // println!("Managing the couple #{}", _parents);
-                // Choose some parents to make a child from them
-                let mut parents_vec = Vec::<Board>::with_capacity(parents);
-                for _ in 0..parents {
-                    // NOTE Since this is not important, we leave the chance for a board to have
-                    // children from itself.
-                    let randomly_picked_parent = survivors_vec[rng.gen_range(0..survivors)].clone();
-                    parents_vec.push(randomly_picked_parent);
-                }
-
-                // Create the child from their stats and distribute the information/genes equally.
-                // In this example implementation, the gene split rate is uniform meaning all
-                // parents pass equal amount of genes to their children.
-                let mut child_genes = Vec::<Point>::with_capacity(n);
-                let gene_portions = n / parents; // number of genes from each parent
-                let last_parent_extra_passing = n % parents; // leftover genes for the last parent
-                for i in 0..parents {
-                    // println!("Subject parent #{}: {}", i, parents[i].queens_display());
-                    let mut extra_genes = 0;
-                    // As for the last parent, give all the remaining genes to the child (may be
-                    // more than others).
-                    if i == parents - 1 {
-                        extra_genes = last_parent_extra_passing;
-                    }
-                    for j in 0..(gene_portions + extra_genes) {
-                        // Pick the first n/PARENTS genes from the first parent then the next till
-                        // one is left
-                        let gene = parents_vec[i].queens()[(i * gene_portions) + j].clone();
-                        // println!("Inheriting {} from parent #{}", gene, i);
-                        child_genes.push(gene);
-                    }
-                }
+    /// Solve the problem using the genetics algorithm over permutations.
+    ///
+    /// Since every child is a permutation, no child is ever discarded for having two queens on
+    /// the same square (or row or column).
+    fn permutation_genetic_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let (config, generations) = match *self {
+            Self::PermutationGenetic {
+                population,
+                survivors,
+                mutation_chance,
+                crossover,
+                generations,
+                ..
+            } => (
+                permutation::Config {
+                    n: self.n(),
+                    population,
+                    survivors,
+                    mutation_chance,
+                    crossover,
+                },
+                generations,
+            ),
+            _ => unreachable!("Invalid variant called the permutation genetic solution"),
+        };
 
-                // Mutate the child genes (move the pieces randomly).
-                // If two pieces collide, mark the board as invalid/cancerous/high-cost (removes it
-                // from the next generation).
-                for i in child_genes.iter_mut() {
-                    let chance = rng.gen_range(0..100);
-                    let mutated_coord = rng.gen_range(0..n);
-                    // println!(
-                    //     "Mutation: {}>={} ; Mutated Coord: {}",
-                    //     mutation_chance,
-                    //     chance,
-                    //     (mutated_coord + 1) // Point is +1 in its diplay
-                    // );
-                    if chance < (mutation_chance / 2) {
-                        // 50% to mutate the row
-                        i.row = mutated_coord;
-                    } else if chance < mutation_chance {
-                        // 50% to mutate the col
-                        i.col = mutated_coord;
-                    }
-                    // println!("{} -> {}", pre, i);
-                }
-                // Check for cancer (two pieces in the same coord)
-                // Try to place the genes on a new board and if successful add it to the env, else
-                // leave the child to die (cancerous).
-                let mut child = Board::new(n);
-                for i in child_genes {
-                    if child.place(&i).is_err() {
-                        continue 'child_production;
-                    }
-                }
+        println!(
+            "Environment details:\n\
+             - population: {}\n\
+             - each generation's survivors: {}\n\
+             - mutation chance: {}%\n\
+             - crossover: {}\n\
+             - maximum generations: {generations}\n\
+            ",
+            config.population, config.survivors, config.mutation_chance, config.crossover,
+        );
+        let mut env = permutation::Population::new(config, rng)?;
 
-                // Healthy child release to the environment.
-                env.push(child);
+        for generation in 0..generations {
+            let (survivors, fittest) = env.evolve(rng);
+            println!(
+                "Generation #{} survivors' heuristics: {:?}",
+                generation, survivors
+            );
+            if let Some(fittest) = fittest {
+                println!("The fittest was found!");
+                println!("{}", fittest);
+                return Ok(());
             }
         }
+        println!("No solution was found in {} generations.", generations);
+        Ok(())
     }
 
     /// Solve the problem using a random placement algorithm.
@@ -242,10 +744,12 @@ impl ModeCommands {
     ///
     /// This is a homework example of a "heuristic function implementation" not an attempt to solve
     /// the N-Queens.
-    pub fn random_solution(&self) {
+    pub fn random_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
         let n = self.n();
 
-        let mut board = Board::new(n).init_n_queens().unwrap();
+        let board = Board::new(n).init_n_queens(rng)?;
+        println!("Queens: {}", board.queens_display());
+        println!("{}", board);
 
         println!(
             "Initial heuristic: {}/{}",
@@ -253,64 +757,608 @@ impl ModeCommands {
             board.max_checks()
         );
 
-        const MAX_MOVES: usize = 100000;
-        println!("Not printing random moves without a heustiric change");
-        for i in 0..MAX_MOVES {
-            let pre_move = board.to_string();
-            let (pre_h, h, from, to) = Self::lower_heuristic(&mut board)
-                .expect("Expected to lower the heuristic by playing moving the most checked queen");
-            let progress = pre_h - h;
+        let mut solver = solver::Random::new(RandomConfig {
+            n,
+            max_moves: 100000,
+        })
+        .start_from(board)
+        .observe(|step| {
+            let progress = step.before - step.after;
             if progress > 0 {
                 println!(
                     "Move #{}: the most checked queen {} -> {} (benefit: -{}h, total: {}h)",
-                    i, from, to, progress, h
+                    step.number, step.from, step.to, progress, step.after
                 );
-                let post_move = board.to_string();
-                let pre = pre_move.split("\n").collect::<Vec<&str>>();
-                let post = post_move.split("\n").collect::<Vec<&str>>();
+                let pre_move = step.previous.to_string();
+                let post_move = step.board.to_string();
+                let pre = pre_move.split('\n').collect::<Vec<&str>>();
+                let post = post_move.split('\n').collect::<Vec<&str>>();
 
                 for i in 0..(pre.len() - 1/* last line is empty */) {
                     println!("{}  -->  {}", pre[i], post[i]);
                 }
                 println!("\n{}\n", "#".repeat(79));
             }
-            if h == 0 {
+        });
+
+        println!("Not printing random moves without a heustiric change");
+        let solution = solver.solve(rng)?;
+        if solution.is_solved() {
+            println!("{}", solution.board);
+            println!("SOLVED!");
+        } else if solution.stats.stuck {
+            println!("{}", solution.board);
+            println!(
+                "Got stuck in a local minimum after {} moves (heuristic: {}).",
+                solution.stats.moves,
+                solution.board.checks_count()
+            );
+        }
+        Ok(())
+    }
+
+    /// Solve the problem using an exact depth-first backtracking search.
+    ///
+    /// Unlike the other modes, this one never gets stuck. It either finds a solution or proves that
+    /// there is none.
+    fn backtrack_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+
+        match backtrack::solve(n) {
+            Some(board) => {
+                println!("Queens: {}", board.queens_display());
                 println!("{}", board);
+                println!("Final heuristic: {}", board.checks_count());
                 println!("SOLVED!");
-                break;
             }
+            None => println!("There is no solution for {n} queens on a {n}x{n} board."),
+        }
+        Ok(())
+    }
+
+    /// Count every solution of the problem instead of finding one.
+    ///
+    /// With more than one thread, the search is split by the placements of the first `depth` rows.
+    /// With `fundamental`, the solutions are grouped by the symmetries of the square and one of
+    /// each group is printed.
+    fn count_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        let (fundamental, threads, depth) = match *self {
+            Self::Count {
+                fundamental,
+                threads,
+                depth,
+                ..
+            } => (fundamental, threads, depth),
+            _ => unreachable!("Invalid variant called the count solution"),
+        };
+
+        let start = Instant::now();
+        if fundamental {
+            let solutions = symmetry::fundamental_solutions(n);
+            for (i, (board, size)) in solutions.iter().enumerate() {
+                println!(
+                    "Fundamental solution #{} (class size: {}): {}",
+                    i + 1,
+                    size,
+                    board.queens_display()
+                );
+            }
+            println!(
+                "There are {} fundamental solutions covering {} solutions for {n} queens on a \
+                 {n}x{n} board (took {:?}).",
+                solutions.len(),
+                solutions.iter().map(|(_, size)| size).sum::<usize>(),
+                start.elapsed()
+            );
+            return Ok(());
+        }
+
+        let total = if threads > 1 {
+            count::count_parallel(n, threads, depth)
+        } else {
+            count::count(n)
+        };
+        println!(
+            "There are {total} solutions for {n} queens on a {n}x{n} board (took {:?}).",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Solve the problem using the min-conflicts local search.
+    ///
+    /// The queens are kept one per column and counted on each line, so this scales to boards far
+    /// larger than what `Board` can hold. The board is only drawn if it fits the screen.
+    fn min_conflicts_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let max_steps = match *self {
+            Self::MinConflicts { max_steps, .. } => max_steps,
+            _ => unreachable!("Invalid variant called the min-conflicts solution"),
+        };
+        const MAX_DRAWN: usize = 32;
+
+        let start = Instant::now();
+        let mut board = MinConflicts::new(n, rng);
+        println!(
+            "Initial heuristic: {} (placed in {:?})",
+            board.checks_count(),
+            start.elapsed()
+        );
+
+        let moves = board.solve(max_steps, rng)?;
+        println!(
+            "Final heuristic: {} after {} moves (took {:?})",
+            board.checks_count(),
+            moves,
+            start.elapsed()
+        );
+        if n <= MAX_DRAWN {
+            println!("{}", board.to_board());
+        }
+        println!("SOLVED!");
+        Ok(())
+    }
+
+    /// Solve the problem using simulated annealing.
+    ///
+    /// The heuristic is the same as the `Random` mode's so the two can be compared on the same
+    /// `n`, but moves which raise it are accepted with a chance that falls with the temperature.
+    fn anneal_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let options = match *self {
+            Self::Anneal {
+                schedule,
+                temperature,
+                cooling_rate,
+                reheat_after,
+                max_steps,
+                ..
+            } => anneal::Options {
+                schedule,
+                initial_temperature: temperature,
+                cooling_rate: cooling_rate.unwrap_or_else(|| schedule.default_rate()),
+                reheat_after,
+                max_steps,
+            },
+            _ => unreachable!("Invalid variant called the anneal solution"),
+        };
+
+        let board = Board::new(n).init_n_queens(rng)?;
+        println!("Queens: {}", board.queens_display());
+        println!("{}", board);
+        println!(
+            "Initial heuristic: {}/{}",
+            board.checks_count(),
+            board.max_checks()
+        );
+        println!(
+            "Annealing with the {} schedule from T={} (rate: {})",
+            options.schedule, options.initial_temperature, options.cooling_rate
+        );
+
+        let report = anneal::anneal(board, &options, rng);
+        println!(
+            "Finished after {} steps ({} uphill moves, {} reheats)",
+            report.steps, report.uphill_moves, report.reheats
+        );
+        println!("{}", report.best);
+        let h = report.best.checks_count();
+        println!("Best heuristic: {}", h);
+        if h == 0 {
+            println!("SOLVED!");
+        }
+        Ok(())
+    }
+
+    /// Solve the problem using tabu search.
+    ///
+    /// Unlike `lower_heuristic`, this remembers the recent moves and takes the best move even if
+    /// it raises the heuristic, so it walks out of the local minima instead of getting stuck.
+    fn tabu_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let options = match *self {
+            Self::Tabu {
+                tenure,
+                max_iterations,
+                ..
+            } => tabu::Options {
+                tenure,
+                max_iterations,
+            },
+            _ => unreachable!("Invalid variant called the tabu solution"),
+        };
+
+        let board = Board::new(n).init_n_queens(rng)?;
+        println!("Queens: {}", board.queens_display());
+        println!("{}", board);
+        println!(
+            "Initial heuristic: {}/{}",
+            board.checks_count(),
+            board.max_checks()
+        );
+
+        let report = tabu::search(board, &options, rng);
+        println!(
+            "Finished after {} iterations ({} tabu moves taken by aspiration)",
+            report.iterations, report.aspirations
+        );
+        println!("{}", report.best);
+        println!("Best heuristic: {}", report.best.checks_count());
+        if let Some(iteration) = report.solved_at {
+            println!("SOLVED! (at iteration #{})", iteration);
         }
+        Ok(())
     }
 
-    /// Move a piece the most checked only if the heuristic shows a lower value.
+    /// Solve the problem without any search using the explicit construction for each `n mod 6`.
     ///
-    /// Breaks after fixed number of attempts.
+    /// The result is verified by marking the taken lines rather than checking every pair. The
+    /// board is only drawn if it fits the screen.
+    fn construct_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        const MAX_DRAWN: usize = 32;
+
+        let start = Instant::now();
+        let cols = match construct::permutation(n) {
+            Some(cols) => cols,
+            None => {
+                println!("There is no solution for {n} queens on a {n}x{n} board.");
+                return Ok(());
+            }
+        };
+        println!("Constructed in {:?}", start.elapsed());
+        assert!(
+            construct::verify(&cols),
+            "The constructed placement has queens checking each other"
+        );
+        println!("Verified in {:?}", start.elapsed());
+        if n <= MAX_DRAWN {
+            println!("{}", Board::from_permutation(&cols)?);
+        }
+        println!("SOLVED!");
+        Ok(())
+    }
+
+    /// Solve the problem using steepest-ascent hill climbing with random restarts.
     ///
-    /// The checks/threats count is the heuristic function in this implementation.
-    fn lower_heuristic(board: &mut Board) -> Result<(usize, usize, Point, Point), &'static str> {
-        const MAX_ATTEMPTS: usize = 1000000;
-        for _ in 0..MAX_ATTEMPTS {
-            let pre_h = board.checks_count();
-            let (from, to) = board.move_most_checked();
-            let post_h = board.checks_count();
-            if pre_h < post_h {
-                board.mov(&to, &from).unwrap();
+    /// Unlike `random_solution`, every legal move is evaluated at each step and a local minimum
+    /// leads to a fresh board instead of a panic.
+    fn hill_climb_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let options = match *self {
+            Self::HillClimb {
+                sideways,
+                max_restarts,
+                ..
+            } => hill_climb::Options {
+                sideways,
+                max_restarts,
+            },
+            _ => unreachable!("Invalid variant called the hill climb solution"),
+        };
+
+        let report = hill_climb::climb(n, &options, rng)?;
+        println!(
+            "Finished after {} restarts and {} moves ({} sideways)",
+            report.restarts, report.moves, report.sideways_moves
+        );
+        println!("{}", report.best);
+        let h = report.best.checks_count();
+        println!("Best heuristic: {}", h);
+        if h == 0 {
+            println!("SOLVED!");
+        }
+        Ok(())
+    }
+
+    /// Solve the problem using (stochastic) local beam search.
+    ///
+    /// Like the `Genetic` mode, this keeps a population of boards, but the next generation is
+    /// made of single-queen moves rather than children of several parents.
+    fn beam_solution(&self, rng: &mut StdRng) -> Result<(), Error> {
+        let n = self.n();
+        let options = match *self {
+            Self::Beam {
+                k,
+                stochastic,
+                max_steps,
+                ..
+            } => beam::Options {
+                k,
+                stochastic,
+                max_steps,
+            },
+            _ => unreachable!("Invalid variant called the beam solution"),
+        };
+
+        let report = beam::search(n, &options, rng)?;
+        println!(
+            "Finished after {} steps of a {}beam of {} boards",
+            report.steps,
+            if options.stochastic {
+                "stochastic "
             } else {
-                return Ok((pre_h, post_h, from, to));
+                ""
+            },
+            options.k
+        );
+        println!("{}", report.best);
+        let h = report.best.checks_count();
+        println!("Best heuristic: {}", h);
+        if h == 0 {
+            println!("SOLVED!");
+        }
+        Ok(())
+    }
+
+    /// Solve the problem as an exact cover with Dancing Links.
+    ///
+    /// Queens may be placed and squares blocked beforehand, the solutions respect both.
+    fn dlx_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        let (count, limit, place, block) = match self {
+            Self::Dlx {
+                count,
+                limit,
+                place,
+                block,
+                ..
+            } => (*count, *limit, place, block),
+            _ => unreachable!("Invalid variant called the dlx solution"),
+        };
+
+        let mut problem = dlx::Problem::new(n);
+        for point in place {
+            problem = problem.place(point.clone());
+        }
+        for point in block {
+            problem = problem.block(point.clone());
+        }
+
+        let start = Instant::now();
+        if count {
+            let total = problem.count()?;
+            println!(
+                "There are {total} solutions for {n} queens on a {n}x{n} board (took {:?}).",
+                start.elapsed()
+            );
+            return Ok(());
+        }
+
+        let solutions = problem.solutions(limit)?;
+        for (i, board) in solutions.iter().enumerate() {
+            println!("Solution #{}: {}", i + 1, board.queens_display());
+            println!("{}", board);
+        }
+        if solutions.is_empty() {
+            println!(
+                "There is no solution for {n} queens on a {n}x{n} board with these constraints."
+            );
+        } else {
+            println!("SOLVED! (took {:?})", start.elapsed());
+        }
+        Ok(())
+    }
+
+    /// Solve the problem as a constraint satisfaction problem.
+    ///
+    /// The number of nodes and backtracks are reported so the heuristics and inferences can be
+    /// compared with each other (or turned off for plain backtracking).
+    fn csp_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        let options = match *self {
+            Self::Csp {
+                inference,
+                no_mrv,
+                no_lcv,
+                ..
+            } => csp::Options {
+                inference,
+                mrv: !no_mrv,
+                lcv: !no_lcv,
+            },
+            _ => unreachable!("Invalid variant called the csp solution"),
+        };
+
+        println!(
+            "Inference: {}, MRV: {}, LCV: {}",
+            options.inference, options.mrv, options.lcv
+        );
+        let start = Instant::now();
+        let report = csp::solve(n, &options);
+        println!(
+            "Expanded {} nodes with {} backtracks (took {:?})",
+            report.nodes,
+            report.backtracks,
+            start.elapsed()
+        );
+        match report.board {
+            Some(board) => {
+                println!("{}", board);
+                println!("SOLVED!");
             }
+            None => println!("There is no solution for {n} queens on a {n}x{n} board."),
         }
-        Err("Got stuck in a local minima")
+        Ok(())
+    }
+
+    /// Solve the problem by reducing it to SAT.
+    ///
+    /// The encoding can be exported for external solvers and their models decoded back into a
+    /// board, otherwise the built-in CDCL solver is used.
+    fn sat_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        let (export, model) = match self {
+            Self::Sat { export, model, .. } => (export, model),
+            _ => unreachable!("Invalid variant called the sat solution"),
+        };
+
+        let cnf = sat::encode(n);
+        println!(
+            "Encoded with {} variables and {} clauses",
+            cnf.vars,
+            cnf.clauses.len()
+        );
+        if let Some(path) = export {
+            fs::write(path, cnf.to_string()).map_err(io_error(path))?;
+            println!("Wrote the DIMACS CNF to {}", path.display());
+        }
+
+        let model = match model {
+            Some(path) => {
+                let s = fs::read_to_string(path).map_err(io_error(path))?;
+                match sat::parse_model(&s) {
+                    Err(Error::Unsatisfiable) => {
+                        println!("The solver found no solution for {n} queens on a {n}x{n} board.");
+                        return Ok(());
+                    }
+                    model => model?,
+                }
+            }
+            None => {
+                let start = Instant::now();
+                let report = sat::solve(&cnf);
+                println!(
+                    "Made {} decisions and learnt from {} conflicts (took {:?})",
+                    report.decisions,
+                    report.conflicts,
+                    start.elapsed()
+                );
+                match report.model {
+                    Some(model) => model,
+                    None => {
+                        println!("There is no solution for {n} queens on a {n}x{n} board.");
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        let board = sat::decode(n, &model)?;
+        println!("{}", board);
+        println!("Final heuristic: {}", board.checks_count());
+        if board.queens().len() == n && board.checks_count() == 0 {
+            println!("SOLVED!");
+        }
+        Ok(())
+    }
+
+    /// Leave the solving to an external tool by writing the model in its format.
+    ///
+    /// With `import`, the tool's answer is read back and checked instead.
+    fn export_solution(&self) -> Result<(), Error> {
+        let n = self.n();
+        let (format, output, place, import) = match self {
+            Self::Export {
+                format,
+                output,
+                place,
+                import,
+                ..
+            } => (*format, output, place, import),
+            _ => unreachable!("Invalid variant called the export solution"),
+        };
+
+        let path = match import {
+            Some(path) => path,
+            None => {
+                let model = export::write(format, n, place)?;
+                match output {
+                    Some(path) => {
+                        fs::write(path, model).map_err(io_error(path))?;
+                        eprintln!("Wrote the {} model to {}", format, path.display());
+                    }
+                    None => print!("{}", model),
+                }
+                return Ok(());
+            }
+        };
+
+        let s = fs::read_to_string(path).map_err(io_error(path))?;
+        let board = export::import(format, n, &s)?;
+        println!("Queens: {}", board.queens_display());
+        println!("{}", board);
+        let h = board.checks_count();
+        println!("Final heuristic: {}", h);
+        if let Some(point) = place.iter().find(|p| board.index_of(p).is_none()) {
+            println!("The answer is missing the placed queen at {}.", point);
+        } else if board.queens().len() == n && h == 0 {
+            println!("SOLVED!");
+        }
+        Ok(())
+    }
+
+    /// Does the mode draw from the random generator (and so needs the seed to be replayed)?
+    fn is_random(&self) -> bool {
+        matches!(
+            self,
+            Self::Random { .. }
+                | Self::MinConflicts { .. }
+                | Self::Anneal { .. }
+                | Self::Tabu { .. }
+                | Self::HillClimb { .. }
+                | Self::Beam { .. }
+                | Self::Genetic { .. }
+                | Self::Islands { .. }
+                | Self::PermutationGenetic { .. }
+        )
     }
 
     fn n(&self) -> usize {
         match self {
             Self::Genetic { n, .. } => *n as usize,
+            Self::Islands { n, .. } => *n as usize,
+            Self::PermutationGenetic { n, .. } => *n as usize,
             Self::Random { n, .. } => *n as usize,
+            Self::Anneal { n, .. } => *n as usize,
+            Self::Tabu { n, .. } => *n as usize,
+            Self::HillClimb { n, .. } => *n as usize,
+            Self::Beam { n, .. } => *n as usize,
+            Self::Dlx { n, .. } => *n as usize,
+            Self::Csp { n, .. } => *n as usize,
+            Self::Sat { n, .. } => *n as usize,
+            Self::Export { n, .. } => *n as usize,
+            Self::Backtrack { n, .. } => *n as usize,
+            Self::Count { n, .. } => *n as usize,
+            Self::MinConflicts { n, .. } => *n as usize,
+            Self::Construct { n, .. } => *n as usize,
         }
     }
 }
 
+/// Read a number of seconds which `Duration` can hold.
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs = s.parse::<f64>().map_err(|e| e.to_string())?;
+    // `Duration::from_secs_f64` panics on the rest.
+    if secs.is_finite() && secs >= 0.0 && secs < u64::MAX as f64 {
+        Ok(Duration::from_secs_f64(secs))
+    } else {
+        Err(format!(
+            "expected a number of seconds from 0 up to {}, got {}",
+            u64::MAX,
+            s
+        ))
+    }
+}
+
+/// Wrap the failure of reading or writing `path` in the library's error.
+fn io_error(path: &Path) -> impl Fn(std::io::Error) -> Error + '_ {
+    move |e| Error::Io {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    }
+}
+
 fn main() {
-    let cli = Cli::parse().mode;
-    cli.solve();
+    let cli = Cli::parse();
+    let seed = cli.seed.unwrap_or_else(|| thread_rng().gen());
+    // Printed apart from the results so the deterministic modes (like `export`) stay clean.
+    if cli.mode.is_random() {
+        eprintln!("Seed: {}", seed);
+    }
+    if let Err(e) = cli.mode.solve(&mut StdRng::seed_from_u64(seed)) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,221 @@
+//! A min-conflicts local search for very large boards.
+//!
+//! `Board` keeps the threats of every pair of queens which makes each move `O(n^2)`. Here, there
+//! is exactly one queen per column and the queens on each row and diagonal are only counted, so
+//! the conflicts of a square are known in constant time and a move is `O(1)` to apply.
+
+use rand::prelude::*;
+
+use crate::{Board, Error, Point};
+
+/// The number of random rows tried for each column before settling for a conflicting one.
+const INIT_TRIES: usize = 100;
+
+/// An `n*n` board with one queen on each column.
+#[derive(Debug, Clone)]
+pub struct MinConflicts {
+    n: usize,
+    /// The row of the queen on each column.
+    rows: Vec<usize>,
+    /// The number of queens on each row.
+    row_counts: Vec<u32>,
+    /// The number of queens on each diagonal indexed by `row + col`.
+    diag_counts: Vec<u32>,
+    /// The number of queens on each anti-diagonal indexed by `row + n - 1 - col`.
+    anti_diag_counts: Vec<u32>,
+}
+
+impl MinConflicts {
+    /// Place a queen on each column with as few conflicts as possible.
+    ///
+    /// The rows are taken from a shuffled pool of unused rows so no two queens share a row, and
+    /// a few rows are tried for each column to avoid the taken diagonals. This leaves only a
+    /// handful of conflicts to repair, even for millions of queens.
+    pub fn new<R: Rng>(n: usize, rng: &mut R) -> Self {
+        let diags = (2 * n).saturating_sub(1);
+        let mut board = Self {
+            n,
+            rows: Vec::with_capacity(n),
+            row_counts: vec![0; n],
+            diag_counts: vec![0; diags],
+            anti_diag_counts: vec![0; diags],
+        };
+
+        let mut unused = (0..n).collect::<Vec<usize>>();
+        for col in 0..n {
+            let mut picked = 0;
+            for _ in 0..INIT_TRIES {
+                picked = rng.gen_range(0..unused.len());
+                if board.conflicts(unused[picked], col) == 0 {
+                    break;
+                }
+            }
+            let row = unused.swap_remove(picked);
+            board.rows.push(row);
+            board.set(row, col, true);
+        }
+        board
+    }
+
+    /// A getter for the rows of the queens on each column.
+    pub fn rows(&self) -> &Vec<usize> {
+        &self.rows
+    }
+
+    /// Add (or remove) a queen to the counters.
+    fn set(&mut self, row: usize, col: usize, add: bool) {
+        let n = self.n;
+        for count in [
+            &mut self.row_counts[row],
+            &mut self.diag_counts[row + col],
+            &mut self.anti_diag_counts[row + n - 1 - col],
+        ] {
+            if add {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+
+    /// The number of queens (other than the one on `col`) which attack a square.
+    fn conflicts(&self, row: usize, col: usize) -> usize {
+        let mut count = self.row_counts[row]
+            + self.diag_counts[row + col]
+            + self.anti_diag_counts[row + self.n - 1 - col];
+        // The queen of this column is not attacking itself.
+        if self.rows.get(col) == Some(&row) {
+            count -= 3;
+        }
+        count as usize
+    }
+
+    /// The number of pairs of queens attacking each other (same as `Board::checks_count`).
+    pub fn checks_count(&self) -> usize {
+        self.row_counts
+            .iter()
+            .chain(&self.diag_counts)
+            .chain(&self.anti_diag_counts)
+            .map(|&k| (k as usize * (k as usize).saturating_sub(1)) / 2)
+            .sum()
+    }
+
+    /// The columns of all the queens which are under attack.
+    fn conflicted(&self) -> Vec<usize> {
+        (0..self.n)
+            .filter(|&col| self.conflicts(self.rows[col], col) > 0)
+            .collect()
+    }
+
+    /// Move a queen to the row of its column with the fewest conflicts (ties broken randomly).
+    ///
+    /// Returns whether the queen left its row.
+    fn move_to_min_conflicts<R: Rng>(&mut self, col: usize, rng: &mut R) -> bool {
+        let mut best = usize::MAX;
+        let mut best_rows = vec![];
+        for row in 0..self.n {
+            let conflicts = self.conflicts(row, col);
+            if conflicts < best {
+                best = conflicts;
+                best_rows.clear();
+            }
+            if conflicts == best {
+                best_rows.push(row);
+            }
+        }
+        let row = *best_rows.choose(rng).expect("There is at least one row");
+        let moved = row != self.rows[col];
+        self.move_to(row, col);
+        moved
+    }
+
+    /// Move the queen of a column to another row.
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.set(self.rows[col], col, false);
+        self.rows[col] = row;
+        self.set(row, col, true);
+    }
+
+    /// Repeatedly move a random conflicted queen to its least conflicted row until none is left.
+    ///
+    /// A queen which is already on its least conflicted row is moved to a random row instead (a
+    /// random walk step), otherwise a local minimum would keep the search in place.
+    ///
+    /// Returns the number of moves it took, or fails after `max_steps` moves.
+    pub fn solve<R: Rng>(&mut self, max_steps: usize, rng: &mut R) -> Result<usize, Error> {
+        // The conflicted queens are only collected again if a move leaves the queen under attack,
+        // since only then the other queens on its new lines have become conflicted.
+        let mut candidates = self.conflicted();
+        let mut moves = 0;
+        while moves < max_steps {
+            if candidates.is_empty() {
+                candidates = self.conflicted();
+                if candidates.is_empty() {
+                    return Ok(moves);
+                }
+            }
+            let i = rng.gen_range(0..candidates.len());
+            let col = candidates[i];
+            if self.conflicts(self.rows[col], col) == 0 {
+                candidates.swap_remove(i);
+                continue;
+            }
+            if !self.move_to_min_conflicts(col, rng) && self.n > 1 {
+                let row = (self.rows[col] + rng.gen_range(1..self.n)) % self.n;
+                self.move_to(row, col);
+            }
+            moves += 1;
+            if self.conflicts(self.rows[col], col) > 0 {
+                candidates = self.conflicted();
+            }
+        }
+        if self.conflicted().is_empty() {
+            Ok(moves)
+        } else {
+            Err(Error::Unsolved)
+        }
+    }
+
+    /// Turn into a regular `Board`.
+    ///
+    /// # Caveats
+    /// - `Board` caches the threats of every pair of queens, this is slow for large `n`.
+    pub fn to_board(&self) -> Board {
+        let queens = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(col, row)| Point::new(*row, col))
+            .collect();
+        Board::with_queens(self.n, queens).expect("There is one queen per column")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_small_and_medium_boards() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in [1, 4, 5, 6, 7, 8, 10, 20, 50, 100] {
+            for _ in 0..20 {
+                let mut board = MinConflicts::new(n, &mut rng);
+                assert!(board.solve(100000, &mut rng).is_ok(), "n = {}", n);
+                assert_eq!(board.to_board().checks_count(), 0, "n = {}", n);
+            }
+        }
+        let mut board = MinConflicts::new(10000, &mut rng);
+        assert!(board.solve(100000, &mut rng).is_ok());
+        assert_eq!(board.checks_count(), 0);
+    }
+
+    #[test]
+    fn small_boards_are_unsolved() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in [2, 3] {
+            let mut board = MinConflicts::new(n, &mut rng);
+            assert_eq!(board.solve(1000, &mut rng), Err(Error::Unsolved));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! The ways the genetic algorithm mutates the genes (queens) of a child.
+//!
+//! Each operator is applied to a single gene, so the mutation chance stays a chance per gene
+//! whatever the operator is.
+
+use rand::prelude::*;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Board, Error, Point};
+
+/// The mutation operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Move the queen to a random row or column (the original mutation).
+    Reset,
+    /// Swap the rows of the queen and another random queen.
+    Swap,
+    /// Reverse the rows of the queens from this one to another random queen.
+    Inversion,
+    /// Shuffle the rows of the queens from this one to another random queen.
+    Scramble,
+    /// Move the queen to the free square with the fewest threats.
+    MinConflicts,
+}
+
+impl Mutation {
+    /// Mutate the gene `i` of the genes of a board of size `n`.
+    pub fn apply<R: Rng>(&self, genes: &mut [Point], i: usize, n: usize, rng: &mut R) {
+        match self {
+            Self::Reset => {
+                let mutated_coord = rng.gen_range(0..n);
+                if rng.gen_bool(0.5) {
+                    genes[i].row = mutated_coord;
+                } else {
+                    genes[i].col = mutated_coord;
+                }
+            }
+            Self::Swap => {
+                let j = rng.gen_range(0..genes.len());
+                let row = genes[i].row;
+                genes[i].row = genes[j].row;
+                genes[j].row = row;
+            }
+            Self::Inversion | Self::Scramble => {
+                let j = rng.gen_range(0..genes.len());
+                let (start, end) = (i.min(j), i.max(j) + 1);
+                let mut rows = genes[start..end]
+                    .iter()
+                    .map(|p| p.row)
+                    .collect::<Vec<usize>>();
+                if *self == Self::Inversion {
+                    rows.reverse();
+                } else {
+                    rows.shuffle(rng);
+                }
+                for (gene, row) in genes[start..end].iter_mut().zip(rows) {
+                    gene.row = row;
+                }
+            }
+            Self::MinConflicts => genes[i] = least_conflicted(genes, i, n, rng),
+        }
+    }
+}
+
+impl FromStr for Mutation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reset" => Ok(Self::Reset),
+            "swap" => Ok(Self::Swap),
+            "inversion" => Ok(Self::Inversion),
+            "scramble" => Ok(Self::Scramble),
+            "min-conflicts" => Ok(Self::MinConflicts),
+            _ => Err(Error::UnknownMutation(s.to_string())),
+        }
+    }
+}
+
+impl Display for Mutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Reset => "reset",
+            Self::Swap => "swap",
+            Self::Inversion => "inversion",
+            Self::Scramble => "scramble",
+            Self::MinConflicts => "min-conflicts",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// The square (not taken by the other genes) threatened by the fewest of the other genes, with
+/// the ties broken randomly.
+fn least_conflicted<R: Rng>(genes: &[Point], i: usize, n: usize, rng: &mut R) -> Point {
+    let others = genes
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .map(|(_, p)| p)
+        .collect::<Vec<&Point>>();
+    let mut best = vec![];
+    let mut best_threats = usize::MAX;
+    for row in 0..n {
+        for col in 0..n {
+            let square = Point::new(row, col);
+            if others.contains(&&square) {
+                continue;
+            }
+            let threats = others
+                .iter()
+                .filter(|p| Board::checking(p, &square))
+                .count();
+            if threats < best_threats {
+                best_threats = threats;
+                best.clear();
+            }
+            if threats == best_threats {
+                best.push(square);
+            }
+        }
+    }
+    best.choose(rng)
+        .cloned()
+        .unwrap_or_else(|| genes[i].clone())
+}
+
+/// Adjusts the mutation chance to the progress of the population.
+///
+/// While the fittest `checks_count` stays the same, the chance grows by `step` each generation
+/// (up to `max`), and once it gets better, the chance halves back towards the initial one.
+#[derive(Debug, Clone)]
+pub struct AdaptiveRate {
+    initial: usize,
+    step: usize,
+    max: usize,
+    chance: usize,
+    best: Option<usize>,
+}
+
+impl AdaptiveRate {
+    pub fn new(initial: usize, step: usize, max: usize) -> Self {
+        Self {
+            initial,
+            step,
+            max: max.max(initial),
+            chance: initial,
+            best: None,
+        }
+    }
+
+    /// The current mutation percentage.
+    pub fn chance(&self) -> usize {
+        self.chance
+    }
+
+    /// Record the fittest `checks_count` of a generation and return the new chance.
+    pub fn update(&mut self, fittest: usize) -> usize {
+        match self.best {
+            Some(best) if fittest >= best => {
+                self.chance = (self.chance + self.step).min(self.max);
+            }
+            _ => {
+                self.best = Some(fittest);
+                self.chance = (self.chance / 2).max(self.initial);
+            }
+        }
+        self.chance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genes() -> Vec<Point> {
+        [(0, 3), (1, 1), (2, 4), (3, 0), (4, 2)]
+            .iter()
+            .map(|&(row, col)| Point::new(row, col))
+            .collect()
+    }
+
+    fn rows(genes: &[Point]) -> Vec<usize> {
+        let mut v = genes.iter().map(|p| p.row).collect::<Vec<usize>>();
+        v.sort_unstable();
+        v
+    }
+
+    #[test]
+    fn row_operators_keep_the_rows_and_columns() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for mutation in [Mutation::Swap, Mutation::Inversion, Mutation::Scramble] {
+            for i in 0..5 {
+                let mut mutated = genes();
+                mutation.apply(&mut mutated, i, 5, &mut rng);
+                assert_eq!(rows(&mutated), rows(&genes()), "{}", mutation);
+                assert!(mutated.iter().zip(genes()).all(|(a, b)| a.col == b.col));
+            }
+        }
+    }
+
+    #[test]
+    fn reset_changes_a_single_gene() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for i in 0..5 {
+            let mut mutated = genes();
+            Mutation::Reset.apply(&mut mutated, i, 5, &mut rng);
+            for (j, (a, b)) in mutated.iter().zip(genes()).enumerate() {
+                assert!(j == i || *a == b);
+            }
+            assert!(mutated[i].row == genes()[i].row || mutated[i].col == genes()[i].col);
+        }
+    }
+
+    #[test]
+    fn min_conflicts_moves_to_a_free_unthreatened_square() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // The last queen is on the diagonal of the first, and some squares are out of every line.
+        let mut genes = vec![Point::new(0, 0), Point::new(1, 2), Point::new(4, 4)];
+        Mutation::MinConflicts.apply(&mut genes, 2, 5, &mut rng);
+        assert!(!genes[..2].contains(&genes[2]));
+        assert!(genes[..2].iter().all(|p| !Board::checking(p, &genes[2])));
+    }
+
+    #[test]
+    fn adaptive_rate_grows_while_stuck() {
+        let mut rate = AdaptiveRate::new(5, 5, 20);
+        assert_eq!(rate.update(10), 5);
+        assert_eq!(rate.update(10), 10);
+        assert_eq!(rate.update(10), 15);
+        assert_eq!(rate.update(10), 20);
+        assert_eq!(rate.update(10), 20);
+        assert_eq!(rate.update(8), 10);
+        assert_eq!(rate.chance(), 10);
+    }
+}
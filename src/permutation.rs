@@ -0,0 +1,277 @@
+//! A genetic algorithm over permutations (one queen per row and column).
+//!
+//! Each individual is the column of the queen on each row, with every column used once, so only
+//! the diagonals can have checks. The crossovers below always make a permutation out of two
+//! permutations, so no child has to be discarded.
+
+use rand::prelude::*;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Board, Error};
+
+/// The ways two parents are combined into a child.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossover {
+    /// Partially-mapped crossover.
+    Pmx,
+    /// Order crossover.
+    Ox,
+    /// Cycle crossover.
+    Cx,
+}
+
+impl Crossover {
+    /// Combine two parents of the same length into a child.
+    pub fn apply<R: Rng>(&self, a: &[usize], b: &[usize], rng: &mut R) -> Vec<usize> {
+        match self {
+            Self::Pmx => pmx(a, b, rng),
+            Self::Ox => ox(a, b, rng),
+            Self::Cx => cx(a, b),
+        }
+    }
+}
+
+impl FromStr for Crossover {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pmx" => Ok(Self::Pmx),
+            "ox" => Ok(Self::Ox),
+            "cx" => Ok(Self::Cx),
+            _ => Err(Error::UnknownPermutationCrossover(s.to_string())),
+        }
+    }
+}
+
+impl Display for Crossover {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Pmx => "pmx",
+            Self::Ox => "ox",
+            Self::Cx => "cx",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A random segment `start..end` of a permutation of length `n`.
+fn segment<R: Rng>(n: usize, rng: &mut R) -> (usize, usize) {
+    let i = rng.gen_range(0..n);
+    let j = rng.gen_range(0..n);
+    (i.min(j), i.max(j) + 1)
+}
+
+/// Partially-mapped crossover.
+///
+/// A segment is copied from `a`, the rest comes from `b` with the values already in the segment
+/// followed through the mapping of the segment until a free one is found.
+pub fn pmx<R: Rng>(a: &[usize], b: &[usize], rng: &mut R) -> Vec<usize> {
+    let n = a.len();
+    let (start, end) = segment(n, rng);
+    let mut child = vec![usize::MAX; n];
+    // Where each value is in `a`.
+    let mut position_in_a = vec![0; n];
+    for (i, &v) in a.iter().enumerate() {
+        position_in_a[v] = i;
+    }
+    child[start..end].copy_from_slice(&a[start..end]);
+    for i in (0..start).chain(end..n) {
+        let mut v = b[i];
+        while (start..end).contains(&position_in_a[v]) {
+            v = b[position_in_a[v]];
+        }
+        child[i] = v;
+    }
+    child
+}
+
+/// Order crossover.
+///
+/// A segment is copied from `a` and the rest is filled with the missing values in the order they
+/// come in `b`, starting after the segment.
+pub fn ox<R: Rng>(a: &[usize], b: &[usize], rng: &mut R) -> Vec<usize> {
+    let n = a.len();
+    let (start, end) = segment(n, rng);
+    let mut child = vec![usize::MAX; n];
+    let mut used = vec![false; n];
+    for i in start..end {
+        child[i] = a[i];
+        used[a[i]] = true;
+    }
+    let mut fill = (end..n).chain(0..start);
+    for k in 0..n {
+        let v = b[(end + k) % n];
+        if !used[v] {
+            child[fill
+                .next()
+                .expect("There is a free place for each missing value")] = v;
+        }
+    }
+    child
+}
+
+/// Cycle crossover.
+///
+/// The positions are split into cycles (following `a`'s value at `b`'s position), and the
+/// cycles are taken from `a` and `b` in turn, so each value keeps the place it had in a parent.
+pub fn cx(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let n = a.len();
+    let mut child = vec![usize::MAX; n];
+    let mut position_in_a = vec![0; n];
+    for (i, &v) in a.iter().enumerate() {
+        position_in_a[v] = i;
+    }
+    let mut from_a = true;
+    for start in 0..n {
+        if child[start] != usize::MAX {
+            continue;
+        }
+        let mut i = start;
+        loop {
+            child[i] = if from_a { a[i] } else { b[i] };
+            i = position_in_a[b[i]];
+            if i == start {
+                break;
+            }
+        }
+        from_a = !from_a;
+    }
+    child
+}
+
+/// The number of pairs of queens on the same diagonals (the `Board::checks_count` of the
+/// permutation, since the rows and columns are never shared).
+pub fn checks_count(cols: &[usize]) -> usize {
+    let n = cols.len();
+    let diags = (2 * n).saturating_sub(1);
+    let mut diag_counts = vec![0; diags];
+    let mut anti_diag_counts = vec![0; diags];
+    for (row, &col) in cols.iter().enumerate() {
+        diag_counts[row + col] += 1;
+        anti_diag_counts[row + n - 1 - col] += 1;
+    }
+    diag_counts
+        .iter()
+        .chain(&anti_diag_counts)
+        .map(|&k: &usize| (k * k.saturating_sub(1)) / 2)
+        .sum()
+}
+
+/// The parameters of a permutation population.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The size of the board and number of queens.
+    pub n: usize,
+    /// The number of individuals in each generation.
+    pub population: usize,
+    /// The number of the fittest individuals which survive and become the parents.
+    pub survivors: usize,
+    /// The percentage chance of a child having two of its queens swap rows.
+    pub mutation_chance: usize,
+    pub crossover: Crossover,
+}
+
+/// The individuals of a generation and how they evolve.
+#[derive(Debug, Clone)]
+pub struct Population {
+    config: Config,
+    individuals: Vec<Vec<usize>>,
+}
+
+impl Population {
+    /// Create a population of random permutations.
+    pub fn new<R: Rng>(config: Config, rng: &mut R) -> Result<Self, Error> {
+        if config.survivors == 0 || config.survivors > config.population {
+            return Err(Error::InvalidSurvivors {
+                survivors: config.survivors,
+                population: config.population,
+            });
+        }
+        let individuals = (0..config.population)
+            .map(|_| {
+                let mut cols = (0..config.n).collect::<Vec<usize>>();
+                cols.shuffle(rng);
+                cols
+            })
+            .collect();
+        Ok(Self {
+            config,
+            individuals,
+        })
+    }
+
+    /// The heuristics of all the individuals.
+    pub fn heuristics(&self) -> Vec<usize> {
+        self.individuals.iter().map(|i| checks_count(i)).collect()
+    }
+
+    /// Keep the fittest and fill the rest of the population with their mutated children.
+    ///
+    /// Returns the heuristics of the survivors and the solution (if one was found).
+    pub fn evolve<R: Rng>(&mut self, rng: &mut R) -> (Vec<usize>, Option<Board>) {
+        let config = &self.config;
+
+        self.individuals.sort_by_cached_key(|i| checks_count(i));
+        self.individuals.truncate(config.survivors);
+        let survivors = self.heuristics();
+        if survivors[0] == 0 {
+            return (
+                survivors,
+                Some(
+                    Board::from_permutation(&self.individuals[0])
+                        .expect("The individuals are permutations"),
+                ),
+            );
+        }
+
+        while self.individuals.len() < config.population {
+            let a = &self.individuals[rng.gen_range(0..config.survivors)];
+            let b = &self.individuals[rng.gen_range(0..config.survivors)];
+            let mut child = config.crossover.apply(a, b, rng);
+            if rng.gen_range(0..100) < config.mutation_chance {
+                let i = rng.gen_range(0..config.n);
+                let j = rng.gen_range(0..config.n);
+                child.swap(i, j);
+            }
+            self.individuals.push(child);
+        }
+        (survivors, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_permutation(cols: &[usize]) -> bool {
+        let mut sorted = cols.to_vec();
+        sorted.sort_unstable();
+        sorted.into_iter().eq(0..cols.len())
+    }
+
+    #[test]
+    fn children_are_permutations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for crossover in [Crossover::Pmx, Crossover::Ox, Crossover::Cx] {
+            for n in 1..=12 {
+                for _ in 0..200 {
+                    let mut a = (0..n).collect::<Vec<usize>>();
+                    let mut b = a.clone();
+                    a.shuffle(&mut rng);
+                    b.shuffle(&mut rng);
+                    let child = crossover.apply(&a, &b, &mut rng);
+                    assert!(
+                        is_permutation(&child),
+                        "{} of {:?} and {:?} gave {:?}",
+                        crossover,
+                        a,
+                        b,
+                        child
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! N-Queens as a boolean satisfiability (SAT) problem.
+//!
+//! Each square is a variable which is true if it has a queen. The encoding asks for at least one
+//! queen per row and at most one queen per row, column and diagonal (each pair of squares on a line
+//! can not both be true). It can be written as DIMACS CNF for external solvers or solved by the
+//! small conflict driven clause learning (CDCL) solver of this module.
+
+use std::fmt::Display;
+
+use crate::{Board, Error, Point};
+
+/// A formula in conjunctive normal form with DIMACS style literals (`v` or `-v`, counting from 1).
+#[derive(Debug, Clone)]
+pub struct Cnf {
+    pub vars: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+/// Writes the formula in the DIMACS CNF format.
+impl Display for Cnf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "p cnf {} {}", self.vars, self.clauses.len())?;
+        for clause in &self.clauses {
+            for lit in clause {
+                write!(f, "{} ", lit)?;
+            }
+            writeln!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// The variable of a square.
+pub fn var(n: usize, point: &Point) -> i32 {
+    (point.row * n + point.col + 1) as i32
+}
+
+/// Encode `n` queens as CNF.
+pub fn encode(n: usize) -> Cnf {
+    let mut clauses = vec![];
+    let mut lines = Vec::<Vec<Point>>::new();
+    for i in 0..n {
+        let row = (0..n).map(|col| Point::new(i, col)).collect::<Vec<Point>>();
+        // At least one queen per row.
+        clauses.push(row.iter().map(|p| var(n, p)).collect());
+        lines.push(row);
+        lines.push((0..n).map(|row| Point::new(row, i)).collect());
+    }
+    for d in 0..(2 * n).saturating_sub(1) {
+        let on_diag = (0..n).filter(|&row| d >= row && d - row < n);
+        lines.push(
+            on_diag
+                .clone()
+                .map(|row| Point::new(row, d - row))
+                .collect(),
+        );
+        lines.push(
+            on_diag
+                .map(|row| Point::new(row, n - 1 - (d - row)))
+                .collect(),
+        );
+    }
+    // At most one queen per line.
+    for line in lines {
+        for i in 0..line.len() {
+            for j in (i + 1)..line.len() {
+                clauses.push(vec![-var(n, &line[i]), -var(n, &line[j])]);
+            }
+        }
+    }
+    Cnf {
+        vars: n * n,
+        clauses,
+    }
+}
+
+/// Read the literals of a model from a SAT solver's output.
+///
+/// Both the competition format (`s` and `v` lines, ignoring `c` lines) and the MiniSat format (a
+/// `SAT` or `UNSAT` line followed by plain numbers) are accepted. The closing `0` is dropped.
+///
+/// Fails with `Error::Unsatisfiable` if the solver found no model.
+pub fn parse_model(s: &str) -> Result<Vec<i32>, Error> {
+    let mut model = vec![];
+    for line in s.lines() {
+        let line = line.trim();
+        let status = line.strip_prefix('s').map_or(line, str::trim);
+        match status {
+            "UNSAT" | "UNSATISFIABLE" => return Err(Error::Unsatisfiable),
+            "SAT" | "SATISFIABLE" => continue,
+            _ => {}
+        }
+        if line.starts_with('c') || line.starts_with('s') {
+            continue;
+        }
+        for word in line.trim_start_matches('v').split_whitespace() {
+            let lit = word
+                .parse::<i32>()
+                .map_err(|_| Error::BadLiteral(word.to_string()))?;
+            if lit != 0 {
+                model.push(lit);
+            }
+        }
+    }
+    Ok(model)
+}
+
+/// Turn a model of the encoding of `n` queens into a `Board`.
+pub fn decode(n: usize, model: &[i32]) -> Result<Board, Error> {
+    let mut queens = vec![];
+    for &lit in model {
+        if lit > (n * n) as i32 || lit < -((n * n) as i32) {
+            return Err(Error::VariableOutOfBounds(lit));
+        }
+        if lit > 0 {
+            let i = lit as usize - 1;
+            queens.push(Point::new(i / n, i % n));
+        }
+    }
+    Board::with_queens(n, queens)
+}
+
+/// The outcome of the CDCL solver.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The model as DIMACS literals, if the formula is satisfiable.
+    pub model: Option<Vec<i32>>,
+    /// The number of decisions made.
+    pub decisions: usize,
+    /// The number of conflicts (and so the learnt clauses).
+    pub conflicts: usize,
+}
+
+/// The literals are `2 * var + sign` internally, with the sign bit set for negations.
+type Lit = usize;
+
+fn lit_var(lit: Lit) -> usize {
+    lit >> 1
+}
+
+fn from_dimacs(lit: i32) -> Lit {
+    let v = (lit.unsigned_abs() - 1) as usize;
+    2 * v + (lit < 0) as usize
+}
+
+/// The `i`th element (counting from 1) of the Luby sequence: 1, 1, 2, 1, 1, 2, 4, ...
+fn luby(mut i: usize) -> usize {
+    loop {
+        let mut k = 1;
+        while (1 << k) - 1 < i {
+            k += 1;
+        }
+        if i == (1 << k) - 1 {
+            return 1 << (k - 1);
+        }
+        i -= (1 << (k - 1)) - 1;
+    }
+}
+
+/// The state of the CDCL solver with two watched literals per clause.
+struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    /// The clauses watching each literal (becoming false).
+    watches: Vec<Vec<usize>>,
+    value: Vec<Option<bool>>,
+    level: Vec<usize>,
+    /// The clause which implied each variable (none for decisions).
+    reason: Vec<Option<usize>>,
+    /// The last value of each variable, reused for the decisions.
+    phase: Vec<bool>,
+    activity: Vec<f64>,
+    bump: f64,
+    trail: Vec<Lit>,
+    /// Where each decision level starts on the trail.
+    trail_lim: Vec<usize>,
+    /// The next literal on the trail to propagate.
+    queue_head: usize,
+}
+
+impl Solver {
+    fn lit_value(&self, lit: Lit) -> Option<bool> {
+        self.value[lit_var(lit)].map(|v| v != (lit & 1 == 1))
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let v = lit_var(lit);
+        self.value[v] = Some(lit & 1 == 0);
+        self.level[v] = self.trail_lim.len();
+        self.reason[v] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Add a clause with at least 2 literals, watching the first two.
+    fn add_clause(&mut self, clause: Vec<Lit>) -> usize {
+        let i = self.clauses.len();
+        self.watches[clause[0] ^ 1].push(i);
+        self.watches[clause[1] ^ 1].push(i);
+        self.clauses.push(clause);
+        i
+    }
+
+    /// Propagate the unit clauses. Returns the conflicting clause (if any).
+    fn propagate(&mut self) -> Option<usize> {
+        while self.queue_head < self.trail.len() {
+            let lit = self.trail[self.queue_head];
+            self.queue_head += 1;
+            let false_lit = lit ^ 1;
+            let watching = std::mem::take(&mut self.watches[lit]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+            for (k, &ci) in watching.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watching[k..]);
+                    break;
+                }
+                if self.clauses[ci][0] == false_lit {
+                    self.clauses[ci].swap(0, 1);
+                }
+                if self.lit_value(self.clauses[ci][0]) == Some(true) {
+                    kept.push(ci);
+                    continue;
+                }
+                let len = self.clauses[ci].len();
+                let other = (2..len).find(|&j| self.lit_value(self.clauses[ci][j]) != Some(false));
+                match other {
+                    Some(j) => {
+                        self.clauses[ci].swap(1, j);
+                        let watched = self.clauses[ci][1];
+                        self.watches[watched ^ 1].push(ci);
+                    }
+                    None => {
+                        kept.push(ci);
+                        let first = self.clauses[ci][0];
+                        if self.lit_value(first) == Some(false) {
+                            conflict = Some(ci);
+                        } else {
+                            self.enqueue(first, Some(ci));
+                        }
+                    }
+                }
+            }
+            self.watches[lit] = kept;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    /// Learn the first unique implication point clause of a conflict.
+    ///
+    /// Returns the clause (asserting literal first) and the level to jump back to.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let current = self.trail_lim.len();
+        let mut seen = vec![false; self.value.len()];
+        let mut learnt = vec![0];
+        let mut pending = 0;
+        let mut clause = conflict;
+        let mut index = self.trail.len();
+        let mut lit = None;
+        loop {
+            // The first literal of a reason clause is the one it implied.
+            let skip = if lit.is_some() { 1 } else { 0 };
+            for j in skip..self.clauses[clause].len() {
+                let q = self.clauses[clause][j];
+                let v = lit_var(q);
+                if !seen[v] && self.level[v] > 0 {
+                    seen[v] = true;
+                    self.activity[v] += self.bump;
+                    if self.level[v] == current {
+                        pending += 1;
+                    } else {
+                        learnt.push(q);
+                    }
+                }
+            }
+            loop {
+                index -= 1;
+                if seen[lit_var(self.trail[index])] {
+                    break;
+                }
+            }
+            let p = self.trail[index];
+            seen[lit_var(p)] = false;
+            pending -= 1;
+            lit = Some(p);
+            if pending == 0 {
+                break;
+            }
+            clause = self.reason[lit_var(p)].expect("Only decisions have no reason");
+        }
+        learnt[0] = lit.expect("The conflict has a literal on the current level") ^ 1;
+
+        // Watch the literal of the highest level after the asserting one.
+        let mut back_level = 0;
+        if learnt.len() > 1 {
+            let max = (1..learnt.len())
+                .max_by_key(|&j| self.level[lit_var(learnt[j])])
+                .unwrap();
+            learnt.swap(1, max);
+            back_level = self.level[lit_var(learnt[1])];
+        }
+        (learnt, back_level)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.trail_lim.len() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for &lit in &self.trail[start..] {
+            let v = lit_var(lit);
+            self.phase[v] = self.value[v].unwrap();
+            self.value[v] = None;
+            self.reason[v] = None;
+        }
+        self.trail.truncate(start);
+        self.trail_lim.truncate(level);
+        self.queue_head = start;
+    }
+
+    /// The unassigned variable with the highest activity.
+    fn pick(&self) -> Option<usize> {
+        (0..self.value.len())
+            .filter(|&v| self.value[v].is_none())
+            .max_by(|&a, &b| self.activity[a].partial_cmp(&self.activity[b]).unwrap())
+    }
+}
+
+/// Solve a formula with the built-in CDCL solver.
+///
+/// It learns a clause from each conflict, jumps back to the level the clause asserts at, picks the
+/// variables which were in the most recent conflicts and restarts on the Luby sequence.
+pub fn solve(cnf: &Cnf) -> Report {
+    let mut solver = Solver {
+        clauses: vec![],
+        watches: vec![vec![]; 2 * cnf.vars],
+        value: vec![None; cnf.vars],
+        level: vec![0; cnf.vars],
+        reason: vec![None; cnf.vars],
+        phase: vec![false; cnf.vars],
+        activity: vec![0.0; cnf.vars],
+        bump: 1.0,
+        trail: vec![],
+        trail_lim: vec![],
+        queue_head: 0,
+    };
+    let mut report = Report {
+        model: None,
+        decisions: 0,
+        conflicts: 0,
+    };
+
+    for clause in &cnf.clauses {
+        let mut lits = clause.iter().map(|&l| from_dimacs(l)).collect::<Vec<Lit>>();
+        lits.sort_unstable();
+        lits.dedup();
+        match lits.len() {
+            0 => return report,
+            1 => match solver.lit_value(lits[0]) {
+                Some(false) => return report,
+                Some(true) => {}
+                None => solver.enqueue(lits[0], None),
+            },
+            _ => {
+                solver.add_clause(lits);
+            }
+        }
+    }
+
+    const RESTART_UNIT: usize = 100;
+    let mut restarts = 1;
+    let mut until_restart = RESTART_UNIT * luby(restarts);
+    loop {
+        if let Some(conflict) = solver.propagate() {
+            report.conflicts += 1;
+            if solver.trail_lim.is_empty() {
+                return report;
+            }
+            let (learnt, level) = solver.analyze(conflict);
+            solver.backtrack(level);
+            if learnt.len() == 1 {
+                solver.enqueue(learnt[0], None);
+            } else {
+                let asserting = learnt[0];
+                let ci = solver.add_clause(learnt);
+                solver.enqueue(asserting, Some(ci));
+            }
+            solver.bump /= 0.95;
+            if solver.bump > 1e100 {
+                solver.activity.iter_mut().for_each(|a| *a *= 1e-100);
+                solver.bump *= 1e-100;
+            }
+            until_restart = until_restart.saturating_sub(1);
+            continue;
+        }
+
+        if until_restart == 0 {
+            restarts += 1;
+            until_restart = RESTART_UNIT * luby(restarts);
+            solver.backtrack(0);
+            continue;
+        }
+
+        match solver.pick() {
+            Some(v) => {
+                report.decisions += 1;
+                solver.trail_lim.push(solver.trail.len());
+                let lit = 2 * v + (!solver.phase[v]) as usize;
+                solver.enqueue(lit, None);
+            }
+            None => {
+                report.model = Some(
+                    (0..cnf.vars)
+                        .map(|v| {
+                            let lit = v as i32 + 1;
+                            if solver.value[v] == Some(true) {
+                                lit
+                            } else {
+                                -lit
+                            }
+                        })
+                        .collect(),
+                );
+                return report;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_boards_are_unsatisfiable() {
+        for n in [2, 3] {
+            assert!(solve(&encode(n)).model.is_none(), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn model_decodes_to_a_solution() {
+        let model = solve(&encode(8)).model.expect("8 queens are satisfiable");
+        let board = decode(8, &model).unwrap();
+        assert_eq!(board.queens().len(), 8);
+        assert_eq!(board.checks_count(), 0);
+    }
+
+    #[test]
+    fn parses_minisat_and_competition_output() {
+        assert_eq!(parse_model("SAT\n-1 2 -3 0\n"), Ok(vec![-1, 2, -3]));
+        assert_eq!(
+            parse_model("c comment\ns SATISFIABLE\nv -1 2\nv -3 0\n"),
+            Ok(vec![-1, 2, -3])
+        );
+        assert_eq!(parse_model("UNSAT\n"), Err(Error::Unsatisfiable));
+        assert_eq!(parse_model("s UNSATISFIABLE\n"), Err(Error::Unsatisfiable));
+    }
+}
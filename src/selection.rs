@@ -0,0 +1,252 @@
+//! The ways the genetic algorithm picks the boards which get to have children.
+//!
+//! Every strategy picks from the costs of the individuals (their `checks_count`, or its shared
+//! form with fitness sharing, lower is fitter). Where a fitness is needed, it is
+//! `1 / (1 + cost)`.
+
+use rand::prelude::*;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::Error;
+
+/// A strategy for picking the mating pool of a generation.
+pub trait Selection: Debug + Send + Sync {
+    /// Pick `count` individuals (by their index in `costs`), possibly the same one several times.
+    fn select(&self, costs: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize>;
+}
+
+fn fitness(cost: f64) -> f64 {
+    1.0 / (1.0 + cost)
+}
+
+/// The indices of `costs` from the fittest to the least fit.
+fn ranked(costs: &[f64]) -> Vec<usize> {
+    let mut indices = (0..costs.len()).collect::<Vec<usize>>();
+    indices.sort_by(|&a, &b| costs[a].total_cmp(&costs[b]));
+    indices
+}
+
+/// Keep the fittest individuals (the original behavior of the genetic mode).
+///
+/// If more individuals are asked for than there are, the ranking is repeated.
+#[derive(Debug, Clone, Copy)]
+pub struct Truncation;
+
+impl Selection for Truncation {
+    fn select(&self, costs: &[f64], count: usize, _rng: &mut dyn RngCore) -> Vec<usize> {
+        ranked(costs).into_iter().cycle().take(count).collect()
+    }
+}
+
+/// The fittest of `size` randomly picked individuals wins each pick.
+#[derive(Debug, Clone, Copy)]
+pub struct Tournament {
+    pub size: usize,
+}
+
+impl Selection for Tournament {
+    fn select(&self, costs: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        if costs.is_empty() {
+            return vec![];
+        }
+        (0..count)
+            .map(|_| {
+                (0..self.size.max(1))
+                    .map(|_| rng.gen_range(0..costs.len()))
+                    .min_by(|&a, &b| costs[a].total_cmp(&costs[b]))
+                    .unwrap()
+            })
+            .collect()
+    }
+}
+
+/// Each pick is made with a chance proportional to the fitness.
+#[derive(Debug, Clone, Copy)]
+pub struct RouletteWheel;
+
+impl Selection for RouletteWheel {
+    fn select(&self, costs: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        weighted(costs.iter().map(|&c| fitness(c)).collect(), count, rng)
+    }
+}
+
+/// Each pick is made with a chance proportional to the rank (the fittest of `k` has weight `k`,
+/// the least fit has weight 1), so large differences of fitness do not take over the pool.
+#[derive(Debug, Clone, Copy)]
+pub struct RankBased;
+
+impl Selection for RankBased {
+    fn select(&self, costs: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let mut weights = vec![0.0; costs.len()];
+        for (rank, i) in ranked(costs).into_iter().enumerate() {
+            weights[i] = (costs.len() - rank) as f64;
+        }
+        weighted(weights, count, rng)
+    }
+}
+
+/// Like the roulette wheel, but all the picks are made with one spin of `count` evenly spaced
+/// pointers, so the number of picks of each individual is close to its expected share.
+#[derive(Debug, Clone, Copy)]
+pub struct StochasticUniversalSampling;
+
+impl Selection for StochasticUniversalSampling {
+    fn select(&self, costs: &[f64], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        if costs.is_empty() || count == 0 {
+            return vec![];
+        }
+        let weights = costs.iter().map(|&c| fitness(c)).collect::<Vec<f64>>();
+        let step = weights.iter().sum::<f64>() / count as f64;
+        let mut pointer = rng.gen_range(0.0..step);
+        let mut picked = Vec::with_capacity(count);
+        let mut sum = 0.0;
+        for (i, w) in weights.iter().enumerate() {
+            sum += w;
+            while pointer < sum && picked.len() < count {
+                picked.push(i);
+                pointer += step;
+            }
+        }
+        // Rounding errors may leave the last pointers past the end.
+        while picked.len() < count {
+            picked.push(costs.len() - 1);
+        }
+        picked
+    }
+}
+
+/// Pick `count` indices with a chance proportional to their weights.
+///
+/// Nothing is picked if no index has a weight.
+fn weighted(weights: Vec<f64>, count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+    let total = weights.iter().sum::<f64>();
+    let last = match weights.iter().rposition(|&w| w > 0.0) {
+        Some(last) if total > 0.0 => last,
+        _ => return vec![],
+    };
+    (0..count)
+        .map(|_| {
+            let mut spin = rng.gen_range(0.0..total);
+            weights
+                .iter()
+                .position(|w| {
+                    spin -= w;
+                    spin < 0.0
+                })
+                // Rounding errors may leave the spin past the last weight.
+                .unwrap_or(last)
+        })
+        .collect()
+}
+
+/// Read a strategy from its name: `truncation`, `tournament[:SIZE]` (2 by default), `roulette`,
+/// `rank` or `sus`.
+pub fn parse(s: &str) -> Result<Arc<dyn Selection>, Error> {
+    let (name, arg) = match s.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (s, None),
+    };
+    match (name, arg) {
+        ("truncation", None) => Ok(Arc::new(Truncation)),
+        ("tournament", arg) => {
+            let size = match arg {
+                Some(arg) => arg
+                    .parse()
+                    .map_err(|_| Error::UnknownSelection(s.to_string()))?,
+                None => 2,
+            };
+            Ok(Arc::new(Tournament { size }))
+        }
+        ("roulette", None) => Ok(Arc::new(RouletteWheel)),
+        ("rank", None) => Ok(Arc::new(RankBased)),
+        ("sus", None) => Ok(Arc::new(StochasticUniversalSampling)),
+        _ => Err(Error::UnknownSelection(s.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COSTS: [f64; 5] = [3.0, 0.0, 5.0, 1.0, 2.0];
+
+    /// How many times each individual of `COSTS` is picked in `count` picks.
+    fn picks(selection: &dyn Selection, count: usize) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(0);
+        let picked = selection.select(&COSTS, count, &mut rng);
+        assert_eq!(picked.len(), count);
+        let mut v = vec![0; COSTS.len()];
+        for i in picked {
+            v[i] += 1;
+        }
+        v
+    }
+
+    #[test]
+    fn truncation_repeats_the_ranking() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            Truncation.select(&COSTS, 7, &mut rng),
+            vec![1, 3, 4, 0, 2, 1, 3]
+        );
+    }
+
+    #[test]
+    fn tournament_of_everyone_picks_the_fittest() {
+        // With replacement, a large tournament almost surely has the fittest in it.
+        let v = picks(&Tournament { size: 50 }, 100);
+        assert_eq!(v[1], 100);
+        // A tournament of one is a uniform pick.
+        assert!(picks(&Tournament { size: 1 }, 1000)
+            .iter()
+            .all(|&k| k > 100));
+    }
+
+    #[test]
+    fn roulette_favours_the_fittest() {
+        let v = picks(&RouletteWheel, 10000);
+        assert!(v[1] > v[3] && v[3] > v[4] && v[4] > v[0] && v[0] > v[2]);
+    }
+
+    #[test]
+    fn rank_based_favours_the_fittest() {
+        let v = picks(&RankBased, 10000);
+        assert!(v[1] > v[3] && v[3] > v[4] && v[4] > v[0] && v[0] > v[2]);
+    }
+
+    #[test]
+    fn sus_picks_close_to_the_expected_share() {
+        let v = picks(&StochasticUniversalSampling, 10000);
+        let total = COSTS.iter().map(|&c| fitness(c)).sum::<f64>();
+        for (i, &c) in COSTS.iter().enumerate() {
+            let expected = 10000.0 * fitness(c) / total;
+            assert!((v[i] as f64 - expected).abs() <= 1.0, "{:?}", v);
+        }
+    }
+
+    #[test]
+    fn weighted_fills_the_count() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Taking the weights off one by one may not bring a spin below zero after rounding.
+        let weights = vec![0.1; 10];
+        assert_eq!(weighted(weights, 1000, &mut rng).len(), 1000);
+        assert!(weighted(vec![0.0; 3], 5, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn parse_reads_every_strategy() {
+        for s in [
+            "truncation",
+            "tournament",
+            "tournament:5",
+            "roulette",
+            "rank",
+            "sus",
+        ] {
+            assert!(parse(s).is_ok(), "{}", s);
+        }
+        assert!(parse("tournament:x").is_err());
+        assert!(parse("best").is_err());
+    }
+}
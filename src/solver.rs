@@ -0,0 +1,308 @@
+//! A common interface for the searches which can be used from other crates.
+//!
+//! Each solver is built from its config, can be given an observer to follow the search (the CLI
+//! prints with it) and returns the best board it found with the details of the run.
+
+use std::time::Duration;
+
+use rand::RngCore;
+
+use crate::genetic::{self, Generation, Population, Progress, StopCriteria, StopReason};
+use crate::{Board, Error, Point};
+
+/// The best board of a search and the details of how it was found.
+#[derive(Debug, Clone)]
+pub struct Solution<S> {
+    pub board: Board,
+    pub stats: S,
+}
+
+impl<S> Solution<S> {
+    /// Has the board no checks?
+    pub fn is_solved(&self) -> bool {
+        self.board.checks_count() == 0
+    }
+}
+
+/// A search for a placement of the queens.
+pub trait Solver {
+    /// The details of a run.
+    type Stats;
+
+    /// Run the search, drawing every random choice from `rng`.
+    fn solve(&mut self, rng: &mut dyn RngCore) -> Result<Solution<Self::Stats>, Error>;
+}
+
+/// The parameters of the random placement (greedy) search.
+#[derive(Debug, Clone)]
+pub struct RandomConfig {
+    /// The size of the board and number of queens.
+    pub n: usize,
+    /// The maximum number of moves.
+    pub max_moves: usize,
+}
+
+/// A move of the random placement search.
+#[derive(Debug)]
+pub struct Step<'a> {
+    /// The number of the move (from 0).
+    pub number: usize,
+    pub from: Point,
+    pub to: Point,
+    /// The heuristic before the move.
+    pub before: usize,
+    /// The heuristic after the move.
+    pub after: usize,
+    /// The board before the move.
+    pub previous: &'a Board,
+    /// The board after the move.
+    pub board: &'a Board,
+}
+
+/// The details of a random placement search.
+#[derive(Debug, Clone)]
+pub struct RandomStats {
+    /// The heuristic of the random placement.
+    pub initial: usize,
+    /// The number of moves made.
+    pub moves: usize,
+    /// The search stopped in a local minimum (see `Board::lower_heuristic`) before solving.
+    pub stuck: bool,
+}
+
+type StepObserver = Box<dyn FnMut(&Step)>;
+
+/// Place the queens randomly and keep moving the most checked queen (if it does not raise the
+/// heuristic).
+pub struct Random {
+    config: RandomConfig,
+    start: Option<Board>,
+    observer: Option<StepObserver>,
+}
+
+impl Random {
+    pub fn new(config: RandomConfig) -> Self {
+        Self {
+            config,
+            start: None,
+            observer: None,
+        }
+    }
+
+    /// Start from the given board instead of a random placement.
+    pub fn start_from(mut self, board: Board) -> Self {
+        self.start = Some(board);
+        self
+    }
+
+    /// Call `observer` after each move.
+    pub fn observe<F: FnMut(&Step) + 'static>(mut self, observer: F) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+}
+
+impl Solver for Random {
+    type Stats = RandomStats;
+
+    fn solve(&mut self, mut rng: &mut dyn RngCore) -> Result<Solution<RandomStats>, Error> {
+        let mut board = match self.start.clone() {
+            Some(board) => board,
+            None => Board::new(self.config.n).init_n_queens(&mut rng)?,
+        };
+        let mut stats = RandomStats {
+            initial: board.checks_count(),
+            moves: 0,
+            stuck: false,
+        };
+        while board.checks_count() != 0 && stats.moves < self.config.max_moves {
+            let previous = self.observer.is_some().then(|| board.clone());
+            let (before, after, from, to) = match board.lower_heuristic(&mut rng) {
+                Ok(step) => step,
+                Err(Error::LocalMinimum) => {
+                    stats.stuck = true;
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            if let (Some(observer), Some(previous)) = (&mut self.observer, &previous) {
+                observer(&Step {
+                    number: stats.moves,
+                    from,
+                    to,
+                    before,
+                    after,
+                    previous,
+                    board: &board,
+                });
+            }
+            stats.moves += 1;
+        }
+        Ok(Solution { board, stats })
+    }
+}
+
+/// The parameters of the genetic search.
+#[derive(Debug, Clone)]
+pub struct GeneticConfig {
+    pub population: genetic::Config,
+    pub criteria: StopCriteria,
+    /// Restart after this many generations without a better board.
+    pub cataclysm: Option<usize>,
+    /// The number of the fittest boards kept by a cataclysm.
+    pub elite: usize,
+}
+
+/// What happened in the genetic search.
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// A generation (counting from 0) evolved.
+    Generation(usize, &'a Generation),
+    /// The population was restarted, keeping the given number of the fittest boards.
+    Cataclysm(usize),
+}
+
+/// The details of a genetic search.
+#[derive(Debug, Clone)]
+pub struct GeneticStats {
+    pub generations: usize,
+    pub cataclysms: usize,
+    pub reason: StopReason,
+    pub elapsed: Duration,
+}
+
+type EventObserver = Box<dyn FnMut(&Event)>;
+
+/// Evolve a population of boards (see `genetic`) until a stop criterion is met.
+pub struct Genetic {
+    config: GeneticConfig,
+    observer: Option<EventObserver>,
+}
+
+impl Genetic {
+    pub fn new(config: GeneticConfig) -> Self {
+        Self {
+            config,
+            observer: None,
+        }
+    }
+
+    /// Call `observer` after each generation and cataclysm.
+    pub fn observe<F: FnMut(&Event) + 'static>(mut self, observer: F) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// A getter for the config.
+    pub fn config(&self) -> &GeneticConfig {
+        &self.config
+    }
+
+    fn notify(&mut self, event: Event) {
+        if let Some(observer) = &mut self.observer {
+            observer(&event);
+        }
+    }
+}
+
+impl Solver for Genetic {
+    type Stats = GeneticStats;
+
+    fn solve(&mut self, mut rng: &mut dyn RngCore) -> Result<Solution<GeneticStats>, Error> {
+        let GeneticConfig {
+            population,
+            criteria,
+            cataclysm,
+            elite,
+        } = self.config.clone();
+        let mut env = Population::new(population, &mut rng)?;
+        let mut progress = Progress::new(criteria);
+        let mut stats = GeneticStats {
+            generations: 0,
+            cataclysms: 0,
+            reason: StopReason::Generations,
+            elapsed: Duration::ZERO,
+        };
+        let mut best = env.fittest(1).pop().ok_or(Error::Extinct)?;
+
+        while stats.generations < self.config.criteria.generations {
+            let generation = env.evolve(&mut rng)?;
+            self.notify(Event::Generation(stats.generations, &generation));
+            stats.generations += 1;
+            if generation.best.checks_count() < best.checks_count() {
+                best = generation.best.clone();
+            }
+            if let Some(reason) = progress.update(&generation) {
+                stats.reason = reason;
+                break;
+            }
+            if cataclysm.map_or(false, |k| progress.stagnant() >= k) {
+                env.cataclysm(elite, &mut rng)?;
+                progress.reset_stagnation();
+                stats.cataclysms += 1;
+                self.notify(Event::Cataclysm(elite));
+            }
+        }
+        stats.elapsed = progress.elapsed();
+        Ok(Solution { board: best, stats })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutation::Mutation;
+    use crate::selection::Truncation;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::sync::Arc;
+
+    fn solve_seeded<S>(solver: &mut dyn Solver<Stats = S>, seed: u64) -> Board {
+        solver
+            .solve(&mut StdRng::seed_from_u64(seed))
+            .unwrap()
+            .board
+    }
+
+    #[test]
+    fn random_replays_with_the_same_seed() {
+        let config = RandomConfig {
+            n: 8,
+            max_moves: 20,
+        };
+        let first = solve_seeded(&mut Random::new(config.clone()), 42);
+        let second = solve_seeded(&mut Random::new(config), 42);
+        assert_eq!(first.queens(), second.queens());
+    }
+
+    #[test]
+    fn genetic_replays_with_the_same_seed() {
+        let config = GeneticConfig {
+            population: genetic::Config {
+                n: 8,
+                population: 20,
+                parents: 2,
+                survivors: 4,
+                selection: Arc::new(Truncation),
+                sharing_radius: None,
+                crossover: genetic::Crossover::Split,
+                mutation_chance: 5,
+                mutation: Mutation::Reset,
+                repair: true,
+                adaptive_mutation: false,
+                moves_in_generation: 0,
+            },
+            criteria: StopCriteria {
+                generations: 20,
+                target: 0,
+                time_limit: None,
+                stagnation: None,
+            },
+            cataclysm: Some(5),
+            elite: 2,
+        };
+        let first = solve_seeded(&mut Genetic::new(config.clone()), 42);
+        let second = solve_seeded(&mut Genetic::new(config), 42);
+        assert_eq!(first.queens(), second.queens());
+    }
+}
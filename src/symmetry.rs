@@ -0,0 +1,155 @@
+//! The symmetries of the square and the fundamental solutions of N-Queens.
+//!
+//! A placement can be rotated by 90 degrees and mirrored, which gives the 8 symmetries of the
+//! square. Two solutions are the same "fundamental" solution if one is a symmetry of the other.
+
+use crate::{count, Board, Point};
+
+impl Board {
+    /// Move every queen (and the cached check data) with the given function.
+    fn map_points<F: Fn(&Point) -> Point>(&self, f: F) -> Self {
+        Self {
+            queens: self.queens.iter().map(&f).collect(),
+            n: self.n,
+            check_data: self
+                .check_data
+                .iter()
+                .map(|threats| threats.iter().map(&f).collect())
+                .collect(),
+            max_checks: self.max_checks,
+        }
+    }
+
+    /// Rotate the board 90 degrees clockwise.
+    pub fn rotate(&self) -> Self {
+        let n = self.n;
+        self.map_points(|p| Point::new(p.col, n - 1 - p.row))
+    }
+
+    /// Mirror the board from left to right.
+    pub fn reflect(&self) -> Self {
+        let n = self.n;
+        self.map_points(|p| Point::new(p.row, n - 1 - p.col))
+    }
+
+    /// All the 8 symmetries of the board, starting with the board itself.
+    ///
+    /// The first 4 are the rotations, the last 4 are the reflections of the rotations.
+    pub fn symmetries(&self) -> Vec<Self> {
+        let mut v = Vec::with_capacity(8);
+        let mut board = self.clone();
+        for _ in 0..4 {
+            let next = board.rotate();
+            v.push(board);
+            board = next;
+        }
+        for i in 0..4 {
+            let reflection = v[i].reflect();
+            v.push(reflection);
+        }
+        v
+    }
+
+    /// The queens ordered by their position (rather than the order they were placed in).
+    pub fn sorted_queens(&self) -> Vec<Point> {
+        let mut queens = self.queens.clone();
+        queens.sort();
+        queens
+    }
+
+    /// The representative of the board among its symmetries.
+    ///
+    /// This is the symmetry with the smallest sorted queens, with the queens kept sorted. All the
+    /// symmetries of a board have the same canonical form.
+    pub fn canonical(&self) -> Self {
+        let board = self
+            .symmetries()
+            .into_iter()
+            .min_by_key(|b| b.sorted_queens())
+            .expect("There are always 8 symmetries");
+        let mut pairs = board
+            .queens
+            .into_iter()
+            .zip(board.check_data)
+            .collect::<Vec<(Point, Vec<Point>)>>();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        let (queens, check_data) = pairs.into_iter().unzip();
+        Self {
+            queens,
+            check_data,
+            ..board
+        }
+    }
+
+    /// The number of distinct placements among the symmetries of the board (1, 2, 4 or 8).
+    pub fn symmetry_class_size(&self) -> usize {
+        let mut placements = self
+            .symmetries()
+            .iter()
+            .map(|b| b.sorted_queens())
+            .collect::<Vec<Vec<Point>>>();
+        placements.sort();
+        placements.dedup();
+        placements.len()
+    }
+}
+
+/// Two boards are equal if they have the same size and queens on the same squares.
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.sorted_queens() == other.sorted_queens()
+    }
+}
+
+impl Eq for Board {}
+
+/// Find one representative of each fundamental solution along with the size of its class.
+///
+/// The representatives are in their canonical form and the class sizes add up to the total
+/// number of solutions (see `count::count`).
+pub fn fundamental_solutions(n: usize) -> Vec<(Board, usize)> {
+    let mut v = vec![];
+    count::for_each_solution(n, |cols| {
+        let board = Board::from_permutation(cols).expect("Every solution is a permutation");
+        let canonical = board.canonical();
+        // Only keep the member of the class which is already in the canonical form.
+        if canonical.queens == board.queens {
+            let size = canonical.symmetry_class_size();
+            v.push((canonical, size));
+        }
+    });
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// OEIS A002562 for n = 1..=10.
+    const KNOWN: [usize; 10] = [1, 0, 0, 1, 2, 1, 6, 12, 46, 92];
+
+    #[test]
+    fn fundamental_solutions_match_oeis() {
+        for (i, &expected) in KNOWN.iter().enumerate() {
+            let n = i + 1;
+            let solutions = fundamental_solutions(n);
+            assert_eq!(solutions.len(), expected, "n = {}", n);
+            let total = solutions
+                .iter()
+                .map(|(_, size)| *size as u128)
+                .sum::<u128>();
+            assert_eq!(total, count::count(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn symmetries_share_the_canonical_form() {
+        for (board, _) in fundamental_solutions(8) {
+            let canonical = board.canonical();
+            for symmetry in board.symmetries() {
+                assert_eq!(symmetry.checks_count(), 0);
+                assert_eq!(symmetry.canonical().queens(), canonical.queens());
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Tabu search over the `Board` using the number of checks as the cost.
+//!
+//! Every iteration takes the best single-queen move, even if it makes the board worse. To avoid
+//! going back and forth between the same placements, a queen may not return to a square it just
+//! left for a number of iterations (the tenure), unless doing so beats the best board seen so far
+//! (the aspiration criteria).
+
+use rand::prelude::*;
+use std::collections::BTreeMap;
+
+use crate::{Board, Point};
+
+/// The parameters of a tabu search.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The number of iterations a queen may not return to a square it left.
+    pub tenure: usize,
+    /// The maximum number of iterations.
+    pub max_iterations: usize,
+}
+
+/// The outcome of a tabu search.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The board with the fewest checks seen.
+    pub best: Board,
+    /// The number of iterations run.
+    pub iterations: usize,
+    /// The iteration at which a solution was found (if any).
+    pub solved_at: Option<usize>,
+    /// The number of tabu moves taken through the aspiration criteria.
+    pub aspirations: usize,
+}
+
+/// Search until no checks are left or the iterations run out.
+pub fn search<R: Rng>(mut board: Board, options: &Options, rng: &mut R) -> Report {
+    // The queens by a fixed id, since moving them changes their order in the board.
+    let mut queens = board.queens().clone();
+    // (queen id, square) -> the first iteration at which the move is allowed again.
+    let mut tabu = BTreeMap::<(usize, Point), usize>::new();
+    let mut cost = board.checks_count();
+    let mut best = board.clone();
+    let mut best_cost = cost;
+    let mut aspirations = 0;
+    let mut solved_at = if cost == 0 { Some(0) } else { None };
+
+    let mut iteration = 0;
+    while iteration < options.max_iterations && solved_at.is_none() {
+        iteration += 1;
+        tabu.retain(|_, until| *until > iteration);
+
+        // Find the best allowed moves as (queen id, destination, is tabu).
+        let mut best_moves = Vec::<(usize, Point, bool)>::new();
+        let mut best_move_cost = usize::MAX;
+        for (id, from) in queens.iter().enumerate() {
+            let moves = board
+                .moves_of(from)
+                .expect("`from` is a queen of the board");
+            for (to, new_cost) in moves {
+                let is_tabu = tabu.contains_key(&(id, to.clone()));
+                if is_tabu && new_cost >= best_cost {
+                    continue;
+                }
+                if new_cost < best_move_cost {
+                    best_move_cost = new_cost;
+                    best_moves.clear();
+                }
+                if new_cost == best_move_cost {
+                    best_moves.push((id, to, is_tabu));
+                }
+            }
+        }
+
+        // Every move may be tabu on tiny boards with long tenures.
+        let (id, to, is_tabu) = match best_moves.choose(rng) {
+            Some(m) => m.clone(),
+            None => continue,
+        };
+        if is_tabu {
+            aspirations += 1;
+        }
+        let from = queens[id].clone();
+        board
+            .mov(&from, &to)
+            .expect("The moves only go to free squares");
+        tabu.insert((id, from), iteration + options.tenure);
+        queens[id] = to;
+        cost = best_move_cost;
+
+        if cost < best_cost {
+            best_cost = cost;
+            best = board.clone();
+            if cost == 0 {
+                solved_at = Some(iteration);
+            }
+        }
+    }
+
+    Report {
+        best,
+        iterations: iteration,
+        solved_at,
+        aspirations,
+    }
+}